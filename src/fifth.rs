@@ -0,0 +1,210 @@
+/*
+    A FIFO queue: push at the back, pop at the front. A singly-linked list can do both in O(1)
+    if it also keeps a pointer to its last node, so push doesn't have to walk there:
+
+    [head][tail]
+      |      \_________________________
+      v                                v
+    (Elem A, next) -> (Elem B, next) -> (Elem C, *null*)
+
+    In safe Rust that tail pointer would have to be a second owner (Rc, like fourth.rs) or a &mut into the
+    list that borrows the list forever. So this one is written with raw pointers, which the borrow checker doesn't track:
+    every node is a heap allocation made by Box and then turned into a NonNull<Node>, and the list frees them itself.
+    The price is that every dereference is unsafe, and keeping the pointers valid is on us instead of the compiler.
+
+    The rules the pointers follow, which are what Miri checks under Stacked Borrows and Tree Borrows:
+    - Every node is only reached through the NonNull pointers made once, when push allocated it. There is no Box or &mut
+      that owns or covers a node while those pointers are in use (a Box head next to a raw tail pointer looks fine, but is UB),
+      and the references peek hands out are reborrowed from the pointer and end before the list can be changed again.
+    - A node goes back into a Box exactly once, in pop, after it has been unlinked from both head and tail.
+    - No pointer is ever built from an integer or cast to one (strict provenance), so every pointer keeps
+      the provenance of its allocation.
+    Each unsafe block says which of these makes it sound.
+*/
+#![deny(clippy::undocumented_unsafe_blocks)]
+
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+pub struct List<T> {
+    head: Link<T>,
+    // the last node, None when the list is empty
+    tail: Link<T>,
+    // NonNull doesn't own anything as far as the compiler knows, this tells drop check that the list owns Ts
+    _owns: PhantomData<T>,
+}
+
+type Link<T> = Option<NonNull<Node<T>>>;
+
+struct Node<T> {
+    elem: T,
+    next: Link<T>,
+}
+
+impl <T> List<T> {
+    pub const fn new() -> Self {
+        List { head: None, tail: None, _owns: PhantomData }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    // O(1): the new node goes straight after the old tail.
+    pub fn push(&mut self, elem: T) {
+        /*
+            Box::leak gives up the Box's ownership, the list has to free the node from here on.
+            The NonNull made from the leaked reference is the one pointer every later access derives from.
+        */
+        let new_tail = NonNull::from(Box::leak(Box::new(Node { elem, next: None })));
+        match self.tail {
+            // SAFETY: tail points at a live node that only this list can reach, and no reference to it is alive
+            Some(old_tail) => unsafe { (*old_tail.as_ptr()).next = Some(new_tail) },
+            None => self.head = Some(new_tail),
+        }
+        self.tail = Some(new_tail);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let head = self.head?;
+        // SAFETY: head came from Box::leak in push, and it is unlinked right below, so it's turned back into a Box exactly once
+        let head = unsafe { Box::from_raw(head.as_ptr()) };
+        self.head = head.next;
+        if self.head.is_none() {
+            // that was the last node, and the tail pointed at it too
+            self.tail = None;
+        }
+        Some(head.elem)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        // SAFETY: a live node, and the reference lives no longer than &self, during which nothing can pop or write it
+        self.head.map(|node| unsafe { &(*node.as_ptr()).elem })
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: as in peek, and &mut self makes this the only reference into the list
+        self.head.map(|node| unsafe { &mut (*node.as_ptr()).elem })
+    }
+}
+
+impl <T> Default for List<T> {
+    fn default() -> Self {
+        List::new()
+    }
+}
+
+// nothing frees the nodes for us: popping them all turns each back into a Box that drops
+impl <T> Drop for List<T> {
+    fn drop(&mut self) {
+        while self.pop().is_some() {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::List;
+    use std::rc::Rc;
+
+    #[test]
+    fn basics() {
+        let mut list = List::new();
+
+        // Check empty list behaves right
+        assert_eq!(list.pop(), None);
+
+        // Populate list
+        list.push(1);
+        list.push(2);
+        list.push(3);
+
+        // Check normal removal
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), Some(2));
+
+        // Push some more just to make sure nothing's corrupted
+        list.push(4);
+        list.push(5);
+
+        // Check normal removal
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), Some(4));
+
+        // Check exhaustion
+        assert_eq!(list.pop(), Some(5));
+        assert_eq!(list.pop(), None);
+
+        // Check the exhaustion case fixed the pointer right
+        list.push(6);
+        list.push(7);
+
+        // Check normal removal
+        assert_eq!(list.pop(), Some(6));
+        assert_eq!(list.pop(), Some(7));
+        assert_eq!(list.pop(), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn peek() {
+        let mut list = List::new();
+        assert_eq!(list.peek(), None);
+        list.push(1);
+        list.push(2);
+        assert_eq!(list.peek(), Some(&1));
+
+        *list.peek_mut().unwrap() = 10;
+        // pushing at the back goes through the tail pointer while the head was just written through peek_mut
+        list.push(3);
+        assert_eq!(list.pop(), Some(10));
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.peek(), Some(&3));
+    }
+
+    #[test]
+    fn drop_frees_nodes() {
+        let tracker = Rc::new(());
+        let mut list = List::new();
+        for _ in 0..3 {
+            list.push(tracker.clone());
+        }
+        list.pop();
+        assert_eq!(Rc::strong_count(&tracker), 3);
+        drop(list);
+        assert_eq!(Rc::strong_count(&tracker), 1);
+    }
+
+    // Interleaves every operation, so that running the tests under Miri checks the aliasing of head and tail.
+    #[test]
+    fn miri_food() {
+        let mut list = List::new();
+
+        list.push(1);
+        list.push(2);
+        list.push(3);
+
+        assert!(list.pop() == Some(1));
+        list.push(4);
+        assert!(list.pop() == Some(2));
+        list.push(5);
+
+        assert!(list.peek() == Some(&3));
+        list.push(6);
+        if let Some(elem) = list.peek_mut() {
+            *elem *= 10;
+        }
+        assert!(list.peek() == Some(&30));
+        assert!(list.pop() == Some(30));
+
+        // the head and the tail are the same node
+        while list.pop().is_some_and(|elem| elem != 5) {}
+        if let Some(elem) = list.peek_mut() {
+            *elem += 10;
+        }
+        list.push(7);
+        assert!(list.pop() == Some(16));
+        assert!(list.pop() == Some(7));
+        assert!(list.pop().is_none());
+        list.push(8);
+    }
+}
@@ -28,18 +28,24 @@ split off C:
 [ptr] -> (Elem C, *null*)
  */
 #[allow(dead_code)]
-pub struct List {
-    root: Link,
+pub struct List<T> {
+    root: Link<T>,
+    // counted on push and pop, so asking for the length doesn't mean walking (or draining) the list
+    len: usize,
 }
+/*
+    second.rs shortens this to Option<Box<Node<T>>>, which has the same layout.
+    It is spelled out here so the two states of a link stay visible.
+*/
 #[allow(dead_code)]
-enum Link {
+enum Link<T> {
     Empty,
-    PointerTo(Box<Node>),
+    PointerTo(Box<Node<T>>),
 }
 #[allow(dead_code)]
-struct Node {
-    elem: i32,
-    next: Link,
+struct Node<T> {
+    elem: T,
+    next: Link<T>,
 }
 
 /*
@@ -49,13 +55,14 @@ This implementation of List allows us to:
     3. all elements uniformly allocated on heap
  */
 
+use std::fmt;
 use std::mem;
-impl List {
-    pub fn new() -> List {
-        List { root: Link::Empty }
+impl <T> List<T> {
+    pub const fn new() -> List<T> {
+        List { root: Link::Empty, len: 0 }
     }
 
-    pub fn push(&mut self, elem: i32) {
+    pub fn push(&mut self, elem: T) {
         let new_node = Box::new(Node {
             elem, 
             next: mem::replace(&mut self.root, Link::Empty)
@@ -65,9 +72,10 @@ impl List {
             This incredibly useful function lets us steal a value out of a borrow by replacing it with another value
          */
         self.root = Link::PointerTo(new_node);
+        self.len += 1;
     }
 
-    pub fn pop(&mut self) -> Option<i32> {
+    pub fn pop(&mut self) -> Option<T> {
         // 2. we should add a reference to the match to ensure ownership is only borrowed, not taken
         // 4. using mem::replace to replace the original root with empty, temporarily taking ownership of root so that we can change self.root to node.next
         match mem::replace(&mut self.root, Link::Empty) {
@@ -78,6 +86,7 @@ impl List {
             */
             Link::PointerTo(node) => {
                 self.root = node.next;
+                self.len -= 1;
                 Some(node.elem)
                 /*
                     3. we are trying to move out of node when we only have a &self.root (shared reference)
@@ -86,6 +95,29 @@ impl List {
             },
         }
     }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        // matching on &self.root only borrows the node, unlike pop which has to take it out
+        self.root.as_node().map(|node| &node.elem)
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.root.as_node_mut().map(|node| &mut node.elem)
+    }
+}
+
+impl <T> Default for List<T> {
+    fn default() -> Self {
+        List::new()
+    }
 }
 
 /*
@@ -95,7 +127,7 @@ impl List {
         &self - shared reference (represents temporary shared access to a value that you don't own)
 */
 
-impl Drop for List {
+impl <T> Drop for List<T> {
     fn drop(&mut self) {
         // 1. replace root with Link::Empty and move root-node-pointer enum into cur_link
         let mut cur_link = mem::replace(&mut self.root, Link::Empty);
@@ -117,6 +149,137 @@ impl Drop for List {
     }
 }
 
+/*
+    second.rs gets these two for free from Option::as_deref and Option::as_deref_mut.
+    With our own enum we have to write them, but they are just a match on the two variants.
+*/
+impl <T> Link<T> {
+    fn as_node(&self) -> Option<&Node<T>> {
+        match self {
+            Link::Empty => None,
+            Link::PointerTo(node) => Some(node),
+        }
+    }
+
+    fn as_node_mut(&mut self) -> Option<&mut Node<T>> {
+        match self {
+            Link::Empty => None,
+            Link::PointerTo(node) => Some(node),
+        }
+    }
+}
+
+// same three iterators as second.rs, reached through IntoIterator, iter() and iter_mut()
+pub struct IntoIter<T>(List<T>);
+
+impl <T> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+impl <'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl <'a, T> IntoIterator for &'a mut List<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl <T> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop()
+    }
+}
+
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl <T> List<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { next: self.root.as_node() }
+    }
+}
+
+impl <'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        // shared references are Copy, so map can copy next out and we overwrite it inside
+        self.next.map(|node| {
+            self.next = node.next.as_node();
+            &node.elem
+        })
+    }
+}
+
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut Node<T>>,
+}
+
+impl <T> List<T> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { next: self.root.as_node_mut() }
+    }
+}
+
+impl <'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<Self::Item> {
+        // &mut is not Copy, so next has to be taken; the node is then split into its two disjoint fields
+        self.next.take().map(|node| {
+            self.next = node.next.as_node_mut();
+            &mut node.elem
+        })
+    }
+}
+
+/*
+    Draws the live list in the notation of layout 2 at the top of this file, e.g. for 1, 2, 3:
+
+    [ptr] -> (1, ptr) -> (2, ptr) -> (3, *null*)
+
+    Every link is drawn from the variant it actually holds, ptr for PointerTo and *null* for Empty,
+    so an empty list is just [*null*].
+*/
+pub struct Diagram<'a, T>(&'a List<T>);
+
+impl <T> List<T> {
+    pub fn diagram(&self) -> Diagram<'_, T> {
+        Diagram(self)
+    }
+}
+
+impl <'a, T: fmt::Display> fmt::Display for Diagram<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn link_name<T>(link: &Link<T>) -> &'static str {
+            match link {
+                Link::Empty => "*null*",
+                Link::PointerTo(_) => "ptr",
+            }
+        }
+
+        write!(f, "[{}]", link_name(&self.0.root))?;
+        let mut link = &self.0.root;
+        while let Link::PointerTo(node) = link {
+            write!(f, " -> ({}, {})", node.elem, link_name(&node.next))?;
+            link = &node.next;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::List;
@@ -148,4 +311,97 @@ mod test {
         assert_eq!(list.pop(), Some(1));
         assert_eq!(list.pop(), None);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn default() {
+        // only needs to compile, new() has to be usable in a static
+        static _EMPTY: List<i32> = List::new();
+
+        let mut list = List::default();
+        assert_eq!(list.pop(), None);
+        list.push(1);
+        assert_eq!(list.pop(), Some(1));
+    }
+
+    #[test]
+    fn generic() {
+        let mut list = List::new();
+        list.push(String::from("a"));
+        list.push(String::from("b"));
+        assert_eq!(list.pop().as_deref(), Some("b"));
+
+        // elements that own heap memory are dropped with the list
+        list.push(String::from("c"));
+    }
+
+    #[test]
+    fn iterators() {
+        let mut list = List::new();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+
+        // looking doesn't take anything out
+        let seen: Vec<_> = list.iter().collect();
+        assert_eq!(seen, vec![&3, &2, &1]);
+
+        for elem in &mut list {
+            *elem *= 10;
+        }
+        let mut iter = list.iter_mut();
+        assert_eq!(iter.next(), Some(&mut 30));
+        assert_eq!(iter.next(), Some(&mut 20));
+        assert_eq!(iter.next(), Some(&mut 10));
+        assert_eq!(iter.next(), None);
+
+        let owned: Vec<_> = list.into_iter().collect();
+        assert_eq!(owned, vec![30, 20, 10]);
+    }
+
+    #[test]
+    fn peek() {
+        let mut list = List::new();
+        assert_eq!(list.peek(), None);
+        assert_eq!(list.peek_mut(), None);
+        list.push(1);
+        list.push(2);
+
+        assert_eq!(list.peek(), Some(&2));
+        if let Some(elem) = list.peek_mut() {
+            *elem = 42;
+        }
+        assert_eq!(list.peek(), Some(&42));
+        assert_eq!(list.pop(), Some(42));
+        assert_eq!(list.peek(), Some(&1));
+    }
+
+    #[test]
+    fn len() {
+        let mut list = List::new();
+        assert_eq!(list.len(), 0);
+        assert!(list.is_empty());
+
+        list.push(1);
+        list.push(2);
+        assert_eq!(list.len(), 2);
+        list.pop();
+        assert_eq!(list.len(), 1);
+        list.pop();
+        // popping an empty list must not underflow
+        list.pop();
+        assert_eq!(list.len(), 0);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn diagram() {
+        let mut list = List::new();
+        assert_eq!(list.diagram().to_string(), "[*null*]");
+
+        list.push('C');
+        assert_eq!(list.diagram().to_string(), "[ptr] -> (C, *null*)");
+        list.push('B');
+        list.push('A');
+        assert_eq!(list.diagram().to_string(), "[ptr] -> (A, ptr) -> (B, ptr) -> (C, *null*)");
+    }
+}
@@ -0,0 +1,504 @@
+/*
+    A doubly-linked deque in safe Rust. Every node is pointed at twice, by the node before it (or the list's head)
+    and by the node after it (or the tail), so a Box won't do: each link is an Rc. Rc only gives shared access though,
+    and pushing or popping has to rewrite the neighbours' links, so each node also sits in a RefCell,
+    which checks borrows at runtime instead of at compile time.
+
+    [head][tail]
+      |      \________________________________
+      v                                       v
+    (Elem A, prev: *null*, next) <-> (Elem B, prev, next) <-> (Elem C, prev, next: *null*)
+
+    Neighbouring nodes point at each other, which makes a reference cycle: their counts never reach 0 by themselves.
+    Drop has to break the links, or every node would leak.
+*/
+use std::cell::{Ref, RefCell, RefMut};
+use std::rc::Rc;
+
+pub struct List<T> {
+    head: Link<T>,
+    tail: Link<T>,
+    // kept in sync by the four push/pop methods, which every other edit goes through or mirrors
+    len: usize,
+}
+
+type Link<T> = Option<Rc<RefCell<Node<T>>>>;
+
+struct Node<T> {
+    elem: T,
+    next: Link<T>,
+    prev: Link<T>,
+}
+
+impl <T> Node<T> {
+    fn new(elem: T) -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Node { elem, next: None, prev: None }))
+    }
+
+    // Only call once the node is unlinked from both sides, so that this is the last Rc to it.
+    fn into_elem(node: Rc<RefCell<Self>>) -> T {
+        /*
+            try_unwrap gives back the RefCell if the count is 1, and into_inner takes the Node out of the cell.
+            ok() is needed before unwrap since Err holds the Rc, and unwrap on a Result needs Err to be Debug.
+        */
+        Rc::try_unwrap(node).ok().unwrap().into_inner().elem
+    }
+}
+
+impl <T> List<T> {
+    pub const fn new() -> Self {
+        List { head: None, tail: None, len: 0 }
+    }
+
+    pub fn push_front(&mut self, elem: T) {
+        let new_head = Node::new(elem);
+        match self.head.take() {
+            Some(old_head) => {
+                // both directions have to be linked: old <- new and new -> old
+                old_head.borrow_mut().prev = Some(new_head.clone());
+                new_head.borrow_mut().next = Some(old_head);
+                self.head = Some(new_head);
+            },
+            None => {
+                // the only node is both ends
+                self.tail = Some(new_head.clone());
+                self.head = Some(new_head);
+            },
+        }
+        self.len += 1;
+    }
+
+    pub fn push_back(&mut self, elem: T) {
+        let new_tail = Node::new(elem);
+        match self.tail.take() {
+            Some(old_tail) => {
+                old_tail.borrow_mut().next = Some(new_tail.clone());
+                new_tail.borrow_mut().prev = Some(old_tail);
+                self.tail = Some(new_tail);
+            },
+            None => {
+                self.head = Some(new_tail.clone());
+                self.tail = Some(new_tail);
+            },
+        }
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.head.take().map(|old_head| {
+            match old_head.borrow_mut().next.take() {
+                Some(new_head) => {
+                    // the new head still points back at the old one
+                    new_head.borrow_mut().prev.take();
+                    self.head = Some(new_head);
+                },
+                None => {
+                    // old_head was the last node, so the tail points at it too
+                    self.tail.take();
+                },
+            }
+            self.len -= 1;
+            Node::into_elem(old_head)
+        })
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.tail.take().map(|old_tail| {
+            match old_tail.borrow_mut().prev.take() {
+                Some(new_tail) => {
+                    new_tail.borrow_mut().next.take();
+                    self.tail = Some(new_tail);
+                },
+                None => {
+                    self.head.take();
+                },
+            }
+            self.len -= 1;
+            Node::into_elem(old_tail)
+        })
+    }
+}
+
+impl <T> Default for List<T> {
+    fn default() -> Self {
+        List::new()
+    }
+}
+
+/*
+    Popping unlinks each node from both neighbours before dropping it, which breaks every cycle.
+    It also keeps the drop iterative, like the other lists.
+*/
+impl <T> Drop for List<T> {
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+/*
+    The element sits inside a RefCell, so a plain &T can't be handed out: the cell has to know it is borrowed.
+    Instead the peeks return the cell's guards, narrowed from the whole Node down to just elem with Ref::map.
+    The guard keeps the cell borrowed, and it also borrows the list, so nothing can be pushed or popped while it is held.
+*/
+impl <T> List<T> {
+    pub fn peek_front(&self) -> Option<Ref<'_, T>> {
+        self.head.as_ref().map(|node| Ref::map(node.borrow(), |node| &node.elem))
+    }
+
+    pub fn peek_back(&self) -> Option<Ref<'_, T>> {
+        self.tail.as_ref().map(|node| Ref::map(node.borrow(), |node| &node.elem))
+    }
+
+    pub fn peek_front_mut(&mut self) -> Option<RefMut<'_, T>> {
+        self.head.as_ref().map(|node| RefMut::map(node.borrow_mut(), |node| &mut node.elem))
+    }
+
+    pub fn peek_back_mut(&mut self) -> Option<RefMut<'_, T>> {
+        self.tail.as_ref().map(|node| RefMut::map(node.borrow_mut(), |node| &mut node.elem))
+    }
+}
+
+pub struct IntoIter<T>(List<T>);
+
+impl <T> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+impl <T> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+}
+
+// With a tail pointer, taking from the back is as cheap as from the front.
+impl <T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.0.pop_back()
+    }
+}
+
+/*
+    Why there is no iter(): an Iter<'a, T> would have to yield Ref<'a, T>s, but the only way to reach the next node
+    is through a Ref on the current one. That Ref lives in the iterator, so nothing derived from it can outlive
+    the call to next(). Rather than hand out guards of guards, the deque is traversed with visitors:
+    the list walks itself, holding each node's borrow only while f looks at it.
+
+    Each step clones the Rc to the next node (a count bump, no element is copied) so the walk holds no borrow
+    on the node before. Because these take &self / &mut self, f can't push or pop meanwhile, so f can't
+    trigger a RefCell panic.
+*/
+impl <T> List<T> {
+    // Calls f on every element from front to back.
+    pub fn for_each<F: FnMut(&T)>(&self, mut f: F) {
+        let mut cur = self.head.clone();
+        while let Some(rc) = cur {
+            let node = rc.borrow();
+            f(&node.elem);
+            cur = node.next.clone();
+        }
+    }
+
+    // Calls f on every element from front to back, allowing it to change them in place.
+    pub fn for_each_mut<F: FnMut(&mut T)>(&mut self, mut f: F) {
+        let mut cur = self.head.clone();
+        while let Some(rc) = cur {
+            let mut node = rc.borrow_mut();
+            f(&mut node.elem);
+            cur = node.next.clone();
+        }
+    }
+}
+
+/*
+    Positional access. With links both ways a position can be reached from either end,
+    so every walk starts from whichever end is closer: at most len / 2 steps.
+*/
+impl <T> List<T> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // The node at index, which has to be < len. Each step clones the next Rc, like for_each.
+    fn node_at(&self, index: usize) -> Rc<RefCell<Node<T>>> {
+        if index < self.len / 2 {
+            let mut node = self.head.clone().unwrap();
+            for _ in 0..index {
+                let next = node.borrow().next.clone().unwrap();
+                node = next;
+            }
+            node
+        } else {
+            let mut node = self.tail.clone().unwrap();
+            for _ in index + 1..self.len {
+                let prev = node.borrow().prev.clone().unwrap();
+                node = prev;
+            }
+            node
+        }
+    }
+
+    /*
+        Calls f on the element at index and returns what f returns, or None if index is out of bounds.
+        A Ref can't be returned here like peek_front does: the node in the middle is only reachable through
+        a temporary Rc, and a guard can't outlive the Rc it borrows. The same goes for get().
+    */
+    pub fn get_with<R, F: FnOnce(&T) -> R>(&self, index: usize, f: F) -> Option<R> {
+        if index >= self.len {
+            return None;
+        }
+        let node = self.node_at(index);
+        let result = f(&node.borrow().elem);
+        Some(result)
+    }
+
+    // A copy of the element at index, or None if index is out of bounds.
+    pub fn get(&self, index: usize) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.get_with(index, T::clone)
+    }
+
+    // Links a new node in at index, shifting everything from there on back by one. Panics if index > len.
+    pub fn insert(&mut self, index: usize, elem: T) {
+        assert!(index <= self.len, "insertion index (is {}) should be <= len (is {})", index, self.len);
+        if index == 0 {
+            return self.push_front(elem);
+        }
+        if index == self.len {
+            return self.push_back(elem);
+        }
+        // somewhere in the middle, so both neighbours exist
+        let next = self.node_at(index);
+        let prev = next.borrow_mut().prev.take().unwrap();
+        let new_node = Node::new(elem);
+        {
+            let mut new = new_node.borrow_mut();
+            new.prev = Some(prev.clone());
+            new.next = Some(next.clone());
+        }
+        prev.borrow_mut().next = Some(new_node.clone());
+        next.borrow_mut().prev = Some(new_node);
+        self.len += 1;
+    }
+
+    // Unlinks the node at index and returns its element, or None if index is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+        if index == 0 {
+            return self.pop_front();
+        }
+        if index == self.len - 1 {
+            return self.pop_back();
+        }
+        let node = self.node_at(index);
+        let prev = node.borrow_mut().prev.take().unwrap();
+        let next = node.borrow_mut().next.take().unwrap();
+        // the neighbours now point at each other instead of at node
+        prev.borrow_mut().next = Some(next.clone());
+        next.borrow_mut().prev = Some(prev);
+        self.len -= 1;
+        Some(Node::into_elem(node))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::List;
+    use std::rc::Rc;
+
+    #[test]
+    fn basics() {
+        let mut list = List::new();
+
+        // Check empty list behaves right
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.pop_back(), None);
+
+        // Populate list
+        list.push_front(1);
+        list.push_front(2);
+        list.push_front(3);
+
+        // Check normal removal
+        assert_eq!(list.pop_front(), Some(3));
+        assert_eq!(list.pop_front(), Some(2));
+
+        // Push some more just to make sure nothing's corrupted
+        list.push_front(4);
+        list.push_front(5);
+
+        // Check normal removal
+        assert_eq!(list.pop_front(), Some(5));
+        assert_eq!(list.pop_front(), Some(4));
+
+        // Check exhaustion
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_front(), None);
+
+        // ---- back -----
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.pop_back(), Some(2));
+
+        list.push_back(4);
+        list.push_back(5);
+        assert_eq!(list.pop_back(), Some(5));
+        assert_eq!(list.pop_back(), Some(4));
+
+        assert_eq!(list.pop_back(), Some(1));
+        assert_eq!(list.pop_back(), None);
+    }
+
+    #[test]
+    fn both_ends() {
+        let mut list = List::new();
+        list.push_back(2);
+        list.push_front(1);
+        list.push_back(3);
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.pop_front(), Some(1));
+        // one node left: it is both the head and the tail
+        assert_eq!(list.pop_back(), Some(2));
+        assert_eq!(list.pop_front(), None);
+    }
+
+    #[test]
+    fn drop_breaks_cycles() {
+        let tracker = Rc::new(());
+        let mut list = List::new();
+        for _ in 0..3 {
+            list.push_back(tracker.clone());
+        }
+        assert_eq!(Rc::strong_count(&tracker), 4);
+        // leaked nodes would keep their elements alive
+        drop(list);
+        assert_eq!(Rc::strong_count(&tracker), 1);
+    }
+
+    #[test]
+    fn peek() {
+        let mut list = List::new();
+        assert!(list.peek_front().is_none());
+        assert!(list.peek_back().is_none());
+        assert!(list.peek_front_mut().is_none());
+        assert!(list.peek_back_mut().is_none());
+
+        list.push_front(1);
+        assert_eq!(*list.peek_front().unwrap(), 1);
+        assert_eq!(*list.peek_back().unwrap(), 1);
+
+        list.push_front(2);
+        list.push_front(3);
+        assert_eq!(&*list.peek_front().unwrap(), &3);
+        assert_eq!(&*list.peek_back().unwrap(), &1);
+
+        *list.peek_front_mut().unwrap() = 30;
+        *list.peek_back_mut().unwrap() *= 10;
+        assert_eq!(*list.peek_front().unwrap(), 30);
+        assert_eq!(list.pop_back(), Some(10));
+        assert_eq!(*list.peek_back().unwrap(), 2);
+    }
+
+    #[test]
+    fn into_iter() {
+        let mut list = List::new();
+        list.push_front(1);
+        list.push_front(2);
+        list.push_front(3);
+
+        let mut iter = list.into_iter();
+        assert_eq!(iter.next(), Some(3));
+        assert_eq!(iter.next_back(), Some(1));
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.next(), None);
+
+        let mut list = List::new();
+        list.push_back(1);
+        list.push_back(2);
+        assert_eq!(list.into_iter().rev().collect::<Vec<_>>(), vec![2, 1]);
+    }
+
+    #[test]
+    fn for_each() {
+        let mut list = List::new();
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+
+        list.for_each_mut(|elem| *elem *= 10);
+        let mut seen = Vec::new();
+        list.for_each(|elem| seen.push(*elem));
+        assert_eq!(seen, vec![10, 20, 30]);
+
+        // reading the list again inside the visitor is fine, the borrows are all shared
+        let mut fronts = 0;
+        list.for_each(|_| fronts += *list.peek_front().unwrap());
+        assert_eq!(fronts, 30);
+
+        // nothing was consumed
+        assert_eq!(list.pop_front(), Some(10));
+        List::<i32>::new().for_each(|_| panic!("empty list has no elements"));
+    }
+
+    #[test]
+    fn positional() {
+        let mut list = List::new();
+        assert_eq!(list.len(), 0);
+        assert!(list.is_empty());
+        for elem in [1, 2, 4, 5] {
+            list.push_back(elem);
+        }
+        assert_eq!(list.len(), 4);
+
+        // from the back half
+        list.insert(2, 3);
+        // and at both ends
+        list.insert(0, 0);
+        list.insert(6, 6);
+        let mut seen = Vec::new();
+        list.for_each(|elem| seen.push(*elem));
+        assert_eq!(seen, vec![0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(list.len(), 7);
+
+        assert_eq!(list.get(1), Some(1));
+        assert_eq!(list.get(5), Some(5));
+        assert_eq!(list.get(7), None);
+        assert_eq!(list.get_with(3, |elem| elem * 10), Some(30));
+
+        assert_eq!(list.remove(1), Some(1));
+        assert_eq!(list.remove(4), Some(5));
+        assert_eq!(list.remove(0), Some(0));
+        assert_eq!(list.remove(3), Some(6));
+        assert_eq!(list.remove(3), None);
+        assert_eq!(list.len(), 3);
+
+        // the links are consistent in both directions
+        assert_eq!(list.pop_back(), Some(4));
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.pop_front(), Some(2));
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_past_the_end() {
+        let mut list = List::new();
+        list.push_back(1);
+        list.insert(2, 0);
+    }
+}
@@ -1,5 +1,8 @@
 pub mod first;
 pub mod second;
+pub mod third;
+pub mod fourth;
+pub mod fifth;
 
 pub fn add(left: u64, right: u64) -> u64 {
     left + right
@@ -1,6 +1,11 @@
 #[allow(dead_code)]
 pub struct List <T> {
     root: Link<T>,
+    /*
+        Cached element count so len() doesn't have to walk the chain.
+        Every method that links or unlinks nodes has to keep this in sync.
+    */
+    len: usize,
 }
 
 type Link<T> = Option<Box<Node<T>>>;
@@ -13,28 +18,50 @@ type Link<T> = Option<Box<Node<T>>>;
 struct Node<T> {
     elem: T,
     next: Link<T>,
+    // stamped once when the node is allocated and never reused, this is what a NodeHandle refers to
+    id: u64,
+}
+
+// one counter for all lists, so ids stay unique when nodes move between lists (append, split_off, ...)
+static NEXT_NODE_ID: AtomicU64 = AtomicU64::new(0);
+
+impl <T> Node<T> {
+    fn new(elem: T, next: Link<T>) -> Box<Node<T>> {
+        // Relaxed is enough, the counter only has to hand out distinct values
+        Box::new(Node { elem, next, id: NEXT_NODE_ID.fetch_add(1, AtomicOrdering::Relaxed) })
+    }
 }
 
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FusedIterator;
 use std::mem;
+use std::ops::{Bound, Index, IndexMut, RangeBounds};
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
 impl <T> List <T> {
-    pub fn new() -> List<T> {
-        List { root: Link::None }
+    // const so an empty list can be built at compile time, e.g. in a static
+    pub const fn new() -> List<T> {
+        List { root: Link::None, len: 0 }
     }
 
     pub fn push(&mut self, elem: T) {
-        let new_node = Box::new(Node {
+        let new_node = Node::new(
             elem, 
-            next: self.root.take()
+            self.root.take()
             /*
                 Usage of mem::replace is so common, that Option makes it a method take()
             */
-        });
+        );
         self.root = Link::Some(new_node);
+        self.len += 1;
     }
 
     pub fn pop(&mut self) -> Option<T> {
         self.root.take().map(|node| {
             self.root = node.next;
+            self.len -= 1;
             node.elem
         })
         /*
@@ -58,34 +85,130 @@ impl <T> List <T> {
             &mut node.elem
         })
     }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // node-level push and pop, for operations that move whole nodes between lists instead of elements
+    fn push_node(&mut self, mut node: Box<Node<T>>) {
+        node.next = self.root.take();
+        self.root = Link::Some(node);
+        self.len += 1;
+    }
+
+    fn pop_node(&mut self) -> Option<Box<Node<T>>> {
+        self.root.take().map(|mut node| {
+            self.root = node.next.take();
+            self.len -= 1;
+            node
+        })
+    }
 }
 
 
-impl <T> Drop for List<T> {
-    fn drop(&mut self) {
+impl <T> Default for List<T> {
+    fn default() -> Self {
+        List::new()
+    }
+}
+
+impl <T> List<T> {
+    /*
+        Same loop as the first list's Drop: each node's next is taken before the node goes out of scope,
+        so dropping never recurses down the chain.
+    */
+    pub fn clear(&mut self) {
         let mut cur_link = self.root.take();
         while let Link::Some(mut boxed_node) = cur_link {
-            cur_link = mem::replace(&mut boxed_node.next, Link::None);
+            cur_link = boxed_node.next.take();
         }
+        self.len = 0;
+    }
+}
+
+impl <T> Drop for List<T> {
+    fn drop(&mut self) {
+        self.clear();
     }
 }
 
 #[allow(dead_code)]
 /*
     Tuple structs - trivial wrappers around other types without having to name each field   
+    The VecDeque only fills up once next_back is called, see the DoubleEndedIterator impls further down.
 */
-pub struct IntoIter<T>(List<T>);
+pub struct IntoIter<T>(List<T>, VecDeque<T>);
 
-impl <T> List<T> {
-    pub fn into_iter(self) -> IntoIter<T> {
-        IntoIter(self)
+/*
+    into_iter used to be an inherent method, which meant `for x in list` didn't work.
+    Implementing the IntoIterator trait (for all three receiver forms) is what for loops and generic code look for.
+*/
+impl <T> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self, VecDeque::new())
+    }
+}
+
+impl <'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl <'a, T> IntoIterator for &'a mut List<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
     }
 }
 
 impl <T> Iterator for IntoIter<T> {
     type Item = T;
     fn next (&mut self) -> Option<Self::Item> {
-        self.0.pop()
+        self.0.pop().or_else(|| self.1.pop_front())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.len + self.1.len();
+        (len, Some(len))
+    }
+}
+
+impl <T> IntoIter<T> {
+    /*
+        Mirrors the nightly Iterator::advance_by: drops up to n elements,
+        returning Err with the number of steps that could not be taken if the list runs out first.
+    */
+    pub fn advance_by(&mut self, n: usize) -> Result<(), usize> {
+        for step in 0..n {
+            if self.0.pop_node().is_none() && self.1.pop_front().is_none() {
+                return Err(n - step);
+            }
+        }
+        Ok(())
+    }
+
+    /*
+        Reverses whatever is left to iterate by relinking once, so the rest comes out tail first in O(1) per element,
+        without going through next_back. list.into_iter_back() is the same thing for a whole list.
+    */
+    pub fn rev_in_place(mut self) -> IntoIter<T> {
+        // anything next_back already buffered sits behind the list, so it goes in front of the reversed list
+        self.0.reverse();
+        for elem in self.1.drain(..) {
+            self.0.push(elem);
+        }
+        self
     }
 }
 
@@ -95,6 +218,10 @@ pub struct Iter<'a, T> {
         Iter contains a reference to something, we need a lifetime specifier to ensure that reference lasts as long as needed
         Iter is generic over *some* lifetime, it doesn't care
     */
+    // number of elements left on the chain, the chain may run on past them when this Iter covers only part of a list
+    len: usize,
+    // filled once by the first next_back, both ends are served from here afterwards
+    buffered: VecDeque<&'a T>,
 }
 
 //No life time is needed on List because it doesn't have any associated lifetimes
@@ -113,7 +240,32 @@ impl <T> List<T> {
                 - we would typically need to dereference the extra indirection, 
                   but Rust helps us with this with the as_deref() function, dereferencing the extra pointer
         */
-        Iter { next: self.root.as_deref().map(|node| { &*node })}
+        Iter { next: self.root.as_deref(), len: self.len, buffered: VecDeque::new() }
+    }
+}
+
+impl <'a, T> Iter<'a, T> {
+    /*
+        Looking ahead needs no buffering like std's Peekable: the rest of the chain is right there,
+        so peek_nth just walks k nodes from the current one without moving the iterator.
+    */
+    pub fn peek(&self) -> Option<&'a T> {
+        self.peek_nth(0)
+    }
+
+    pub fn peek_nth(&self, k: usize) -> Option<&'a T> {
+        if let Some(&elem) = self.buffered.get(k) {
+            return Some(elem);
+        }
+        let k = k - self.buffered.len();
+        if k >= self.len {
+            return None;
+        }
+        let mut node = self.next?;
+        for _ in 0..k {
+            node = node.next.as_deref()?;
+        }
+        Some(&node.elem)
     }
 }
 
@@ -123,20 +275,37 @@ impl <'a, T> Iterator for Iter<'a, T> {
     type Item = &'a T;
     //code here does not need change due to Self::Item
     fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return self.buffered.pop_front();
+        }
         self.next.map(|node| {
-            self.next = node.next.as_deref().map(|node| &*node);
+            self.next = node.next.as_deref();
+            self.len -= 1;
             &node.elem
         })
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len + self.buffered.len();
+        (len, Some(len))
+    }
 }
 
 pub struct IterMut<'a, T> {
     next: Option<&'a mut Node<T>>,
+    /*
+        Elements already split off their nodes, yielded before anything in next.
+        A &mut Node covers the whole rest of the chain, so this is the only safe way for an IterMut
+        to cover just part of the list (see split_iter_mut).
+    */
+    buffered: VecDeque<&'a mut T>,
+    // number of nodes left in next, not counting buffered
+    len: usize,
 }
 
 impl <T> List<T> {
     pub fn iter_mut<'a>(&'a mut self) -> IterMut<'a, T> {
-        IterMut { next: self.root.as_deref_mut()}
+        IterMut { next: self.root.as_deref_mut(), buffered: VecDeque::new(), len: self.len }
     }
 }
 
@@ -152,11 +321,20 @@ impl <'a, T> Iterator for IterMut<'a, T> {
             - a mutable reference, &mut, is NOT Copy, thus we need to .take() the Option
 
         */
+        if let Some(elem) = self.buffered.pop_front() {
+            return Some(elem);
+        }
         self.next.take().map(|node| {
             self.next = node.next.as_deref_mut();
+            self.len -= 1;
             &mut node.elem
         })
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.buffered.len() + self.len;
+        (len, Some(len))
+    }
 }
 
 /* 
@@ -173,94 +351,3401 @@ impl <'a, T> Iterator for IterMut<'a, T> {
     You can even make the iterator DoubleEnded, so that you can consume the iterator from the front and the back at once! Woah!
 */
 
-#[cfg(test)]
-mod test {
-    use super::List;
-    #[test]
-    fn basics() {
-        let mut list = List::new();
+impl <T> List<T> {
+    /*
+        Reverses a detached chain by popping each node off the front and pushing it onto a new head.
+        No node is reallocated, only the next pointers are rewritten.
+    */
+    fn reverse_link(mut link: Link<T>) -> Link<T> {
+        let mut reversed = Link::None;
+        while let Some(mut node) = link {
+            link = node.next.take();
+            node.next = reversed;
+            reversed = Some(node);
+        }
+        reversed
+    }
 
-        // Check None list behaves right
-        assert_eq!(list.pop(), None);
+    /*
+        Reverses every consecutive group of k nodes, e.g. k = 2 turns [1, 2, 3, 4, 5] into [2, 1, 4, 3, 5].
+        A trailing group with fewer than k nodes is left in its original order.
+        k of 0 or 1 leaves the list untouched.
+    */
+    pub fn reverse_in_groups(&mut self, k: usize) {
+        if k <= 1 {
+            return;
+        }
+        let mut rest = self.root.take();
+        // tail always points at the empty link after the last regrouped node
+        let mut tail = &mut self.root;
+        while rest.is_some() {
+            let mut group = Link::None;
+            let mut count = 0;
+            while count < k {
+                match rest {
+                    Some(mut node) => {
+                        rest = node.next.take();
+                        node.next = group;
+                        group = Some(node);
+                        count += 1;
+                    },
+                    None => break,
+                }
+            }
+            if count < k {
+                // short trailing group, undo the reversal to keep it intact
+                group = List::reverse_link(group);
+            }
+            *tail = group;
+            while let Some(node) = tail {
+                tail = &mut node.next;
+            }
+        }
+    }
+}
 
-        // Populate list
-        list.push(1);
-        list.push(2);
-        list.push(3);
+impl <T> List<T> {
+    /*
+        Merges two already-sorted chains into one by relinking nodes, taking from a on ties so the merge is stable.
+        This is the building block for merge() and the merge sort.
+    */
+    fn merge_links<F>(mut a: Link<T>, mut b: Link<T>, cmp: &mut F) -> Link<T>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut head = Link::None;
+        let mut tail = &mut head;
+        while let (Some(x), Some(y)) = (&a, &b) {
+            let from = if cmp(&x.elem, &y.elem) == Ordering::Greater { &mut b } else { &mut a };
+            let mut node = from.take().unwrap();
+            *from = node.next.take();
+            tail = &mut tail.insert(node).next;
+        }
+        // at most one of the chains is left, it is already sorted so link it as a whole
+        *tail = if a.is_some() { a } else { b };
+        head
+    }
 
-        // Check normal removal
-        assert_eq!(list.pop(), Some(3));
-        assert_eq!(list.pop(), Some(2));
+    /*
+        Both lists are assumed to be sorted ascending from the head.
+        No element is copied, the nodes of both lists are relinked into the result in O(n + m).
+    */
+    pub fn merge(self, other: List<T>) -> List<T>
+    where
+        T: Ord,
+    {
+        self.merge_by(other, T::cmp)
+    }
 
-        // Push some more just to make sure nothing's corrupted
-        list.push(4);
-        list.push(5);
+    /*
+        merge() with a custom comparator, for element types that aren't Ord.
+        The merge is stable: when two elements compare equal, the one from self comes first.
+    */
+    pub fn merge_by<F: FnMut(&T, &T) -> Ordering>(mut self, mut other: List<T>, mut cmp: F) -> List<T> {
+        let len = self.len + other.len;
+        let root = List::merge_links(self.root.take(), other.root.take(), &mut cmp);
+        List { root, len }
+    }
+}
 
-        // Check normal removal
-        assert_eq!(list.pop(), Some(5));
-        assert_eq!(list.pop(), Some(4));
+impl <T> List<T> {
+    /*
+        Hands out two IterMuts over [0, at) and [at, len).
+        Destructuring a &mut Node into &mut elem and &mut next borrows the two fields disjointly,
+        so the first half is split into element references while the second half keeps the rest of the chain.
+        If at is past the end, the second iterator is empty.
+    */
+    pub fn split_iter_mut(&mut self, at: usize) -> (IterMut<'_, T>, IterMut<'_, T>) {
+        let len = self.len;
+        let mut buffered = VecDeque::with_capacity(at.min(len));
+        let mut cur = self.root.as_deref_mut();
+        while buffered.len() < at {
+            match cur {
+                Some(Node { elem, next, .. }) => {
+                    buffered.push_back(elem);
+                    cur = next.as_deref_mut();
+                },
+                None => break,
+            }
+        }
+        let rest = len - buffered.len();
+        (IterMut { next: None, buffered, len: 0 }, IterMut { next: cur, buffered: VecDeque::new(), len: rest })
+    }
+}
 
-        // Check exhaustion
-        assert_eq!(list.pop(), Some(1));
-        assert_eq!(list.pop(), None);
+impl <T> List<T> {
+    /*
+        Exchanges the head nodes of the two lists, each list keeps its own tail.
+        If only one list has a head, that head moves across and the other list ends up one element shorter.
+        If both are empty nothing happens.
+    */
+    pub fn swap_heads(&mut self, other: &mut List<T>) {
+        let ours = self.pop_node();
+        let theirs = other.pop_node();
+        if let Some(node) = theirs {
+            self.push_node(node);
+        }
+        if let Some(node) = ours {
+            other.push_node(node);
+        }
     }
+}
 
-    #[test]
-    fn peek() {
-        let mut list = List::new();
-        assert_eq!(list.peek(), None);
-        assert_eq!(list.peek_mut(), None);
-        list.push(1); list.push(2); list.push(3);
+impl <T> List<T> {
+    /*
+        push() puts each item on top of the last one, so pushing 1, 2, 3 leaves 3 at the head.
+        This instead keeps the iterator's order: the first item becomes the new head
+        and the last item sits right before the old head.
+        The items are linked into a temporary chain through a tail cursor, then the whole chain is spliced in front.
+    */
+    pub fn extend_front_ordered<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut front = Link::None;
+        let mut tail = &mut front;
+        // counted apart from self.len, which must not grow until the chain is actually linked in
+        // (if the iterator panics, front is dropped and self is left as it was)
+        let mut added = 0;
+        for elem in iter {
+            tail = &mut tail.insert(Node::new(elem, Link::None)).next;
+            added += 1;
+        }
+        *tail = self.root.take();
+        self.root = front;
+        self.len += added;
+    }
+}
 
-        assert_eq!(list.peek(), Some(&3));
-        assert_eq!(list.peek_mut(), Some(&mut 3));
-        // list.peek_mut().map(|&mut value| {
-        //     value = 42
-        // });
-        /*
-            The above looks like value should be declared as mutable reference, however, 
-            for closures, it specifies a pattern that will be matched against arguments to the closure.
-            |&mut value| means "the argument is a mutable reference, but just copy it into value"
-        */
-        list.peek_mut().map(|value| {
-            *value = 42
-        });
+impl <T> List<T> {
+    /*
+        Walks the links until one points at a node satisfying f, and returns that link so the caller can relink it.
+        If nothing matches, the empty link at the end of the chain is returned.
+        Checking through as_ref() before stepping keeps each &mut borrow short enough for the borrow checker.
+    */
+    fn find_link<F: FnMut(&T) -> bool>(&mut self, mut f: F) -> &mut Link<T> {
+        let mut cur = &mut self.root;
+        while cur.as_ref().is_some_and(|node| !f(&node.elem)) {
+            cur = &mut cur.as_mut().unwrap().next;
+        }
+        cur
+    }
 
-        assert_eq!(list.peek(), Some(&42));
-        assert_eq!(list.pop(), Some(42));
+    // Removes the first element equal to target, returning whether anything was removed.
+    pub fn remove_value(&mut self, target: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.remove_first_where(|elem| elem == target).is_some()
     }
 
-    #[test]
-    pub fn into_iter() {
-        let mut list = List::new();
-        list.push(1); list.push(2); list.push(3);
+    // Unlinks and returns the first element matching f.
+    pub fn remove_first_where<F: FnMut(&T) -> bool>(&mut self, f: F) -> Option<T> {
+        let link = self.find_link(f);
+        let node = link.take()?;
+        *link = node.next;
+        self.len -= 1;
+        Some(node.elem)
+    }
 
-        let mut iter = list.into_iter();
-        assert_eq!(iter.next(), Some(3));
-        assert_eq!(iter.next(), Some(2));
-        assert_eq!(iter.next(), Some(1));
-        assert_eq!(iter.next(), None);
+    // Unlinks every element matching f and returns how many were removed.
+    pub fn remove_all_where<F: FnMut(&T) -> bool>(&mut self, mut f: F) -> usize {
+        let before = self.len;
+        self.retain(|elem| !f(elem));
+        before - self.len
     }
+}
 
-    #[test]
-    fn iter() {
-        let mut list = List::new();
-        list.push(1); list.push(2); list.push(3);
+#[cfg(debug_assertions)]
+impl <T: std::fmt::Debug> List<T> {
+    /*
+        Renders the live heap layout in the notation used in first.rs:
 
-        let mut iter = list.iter();
-        assert_eq!(iter.next(), Some(&3));
-        assert_eq!(iter.next(), Some(&2));
-        assert_eq!(iter.next(), Some(&1));
+            [ptr] -> (Elem A, ptr) -> (Elem B, ptr) -> (Elem C, *null*)
+
+        Each node is printed as (elem @ own address, next address), so you can see the root pointer
+        and every next pointer match the address of the node after it. An empty list is [null].
+    */
+    pub fn debug_layout(&self) -> String {
+        fn addr<T>(link: &Link<T>) -> String {
+            match link {
+                Some(node) => format!("{:p}", &**node),
+                None => String::from("null"),
+            }
+        }
+
+        let mut out = format!("[{}]", addr(&self.root));
+        let mut cur = self.root.as_deref();
+        while let Some(node) = cur {
+            out.push_str(&format!(" -> ({:?} @ {:p}, {})", node.elem, node, addr(&node.next)));
+            cur = node.next.as_deref();
+        }
+        out
     }
+}
 
-    #[test]
-    fn iter_mut() {
+// Upper bound on the slice length TryFrom<&[T]> accepts, so untrusted input can't make us allocate without bound.
+pub const MAX_SLICE_LEN: usize = 1 << 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryFromSliceError {
+    len: usize,
+}
+
+impl TryFromSliceError {
+    // length of the rejected slice
+    pub fn slice_len(&self) -> usize {
+        self.len
+    }
+}
+
+impl fmt::Display for TryFromSliceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "slice of length {} exceeds the maximum list length of {}", self.len, MAX_SLICE_LEN)
+    }
+}
+
+impl std::error::Error for TryFromSliceError {}
+
+impl <T: Clone> TryFrom<&[T]> for List<T> {
+    type Error = TryFromSliceError;
+
+    // The head of the list is the first element of the slice.
+    fn try_from(slice: &[T]) -> Result<Self, Self::Error> {
+        if slice.len() > MAX_SLICE_LEN {
+            return Err(TryFromSliceError { len: slice.len() });
+        }
         let mut list = List::new();
-        list.push(1); list.push(2); list.push(3);
+        for elem in slice.iter().rev() {
+            list.push(elem.clone());
+        }
+        Ok(list)
+    }
+}
 
-        let mut iter = list.iter_mut();
-        assert_eq!(iter.next(), Some(&mut 3));
-        assert_eq!(iter.next(), Some(&mut 2));
-        assert_eq!(iter.next(), Some(&mut 1));
+impl <T> List<T> {
+    // Compares the elements from the head onwards against any iterator, without building a second list.
+    pub fn iter_eq<I: IntoIterator<Item = T>>(&self, other: I) -> bool
+    where
+        T: PartialEq,
+    {
+        let mut other = other.into_iter();
+        for elem in self.iter() {
+            match other.next() {
+                Some(theirs) if *elem == theirs => {},
+                _ => return false,
+            }
+        }
+        other.next().is_none()
+    }
+}
+
+impl List<char> {
+    // Concatenates the characters from the head onwards.
+    pub fn to_string_collected(&self) -> String {
+        self.iter().collect()
+    }
+}
+
+impl <T> List<T> {
+    // Follows `at` links down the chain, stopping early at the empty link at the end.
+    fn walk_link(mut link: &mut Link<T>, at: usize) -> &mut Link<T> {
+        for _ in 0..at {
+            match link {
+                Some(node) => link = &mut node.next,
+                None => break,
+            }
+        }
+        link
+    }
+
+    // Cuts the chain after `at` links and returns everything past the cut. Past the end, the result is empty.
+    fn split_link(link: &mut Link<T>, at: usize) -> Link<T> {
+        List::walk_link(link, at).take()
+    }
+}
+
+pub struct IntoRChunks<T> {
+    // chunks in head order, yielded from the back
+    chunks: Vec<List<T>>,
+}
+
+impl <T> List<T> {
+    /*
+        Splits the list into lists of n elements aligned to the tail: the first chunk yielded holds the tail-most elements,
+        and if the length isn't a multiple of n the short chunk is at the head and comes out last.
+        Each chunk keeps the original head-first order.
+        A singly-linked list can only be walked from the head, so all chunks are detached up front
+        and the iterator hands them out back to front.
+    */
+    pub fn into_rchunks(mut self, n: usize) -> IntoRChunks<T> {
+        assert!(n != 0, "chunk size must be non-zero");
+        let len = self.len;
+        let mut chunks = Vec::with_capacity(len.div_ceil(n));
+        let mut rest = self.root.take();
+        let mut size = match len % n {
+            0 => n,
+            short => short,
+        };
+        while rest.is_some() {
+            let tail = List::split_link(&mut rest, size);
+            chunks.push(List { root: rest, len: size });
+            rest = tail;
+            size = n;
+        }
+        IntoRChunks { chunks }
+    }
+}
+
+impl <T> Iterator for IntoRChunks<T> {
+    type Item = List<T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunks.pop()
+    }
+}
+
+// Like IntoIter, but yields from the tail (oldest push) to the head.
+pub struct IntoIterBack<T>(List<T>);
+
+impl <T> List<T> {
+    /*
+        There is no way back up a singly-linked chain, so the chain is reversed once by relinking
+        and then popped from the front like IntoIter.
+    */
+    pub fn into_iter_back(mut self) -> IntoIterBack<T> {
+        self.reverse();
+        IntoIterBack(self)
+    }
+}
+
+impl <T> Iterator for IntoIterBack<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop()
+    }
+}
+
+impl <T> List<T> {
+    /*
+        Moves every node of other onto the end of self in order, leaving other empty.
+        Only the last next pointer of self changes; finding it is a walk down self, other isn't traversed at all.
+    */
+    pub fn append(&mut self, other: &mut List<T>) {
+        let tail = self.find_link(|_| false);
+        *tail = other.root.take();
+        self.len += other.len;
+        other.len = 0;
     }
-}
\ No newline at end of file
+}
+
+impl <T> List<T> {
+    /*
+        Splits the list in two at the given index, like std's LinkedList::split_off.
+        self keeps [0, at) and the returned list owns [at, len); the chain is cut by relinking, no element moves.
+        Panics if at > len.
+    */
+    pub fn split_off(&mut self, at: usize) -> List<T> {
+        assert!(at <= self.len, "cannot split off at a nonexistent index");
+        let root = List::split_link(&mut self.root, at);
+        let len = self.len - at;
+        self.len = at;
+        List { root, len }
+    }
+}
+
+impl <T> List<T> {
+    /*
+        Links a new node in at the given index, shifting everything from there on back by one.
+        index == len appends at the end. Panics if index > len, like Vec::insert.
+    */
+    pub fn insert(&mut self, index: usize, elem: T) {
+        assert!(index <= self.len, "insertion index (is {}) should be <= len (is {})", index, self.len);
+        let link = List::walk_link(&mut self.root, index);
+        let next = link.take();
+        *link = Some(Node::new(elem, next));
+        self.len += 1;
+    }
+}
+
+impl <T> List<T> {
+    // Unlinks the node at index and returns its element, or None if index is out of bounds (like VecDeque::remove).
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+        let link = List::walk_link(&mut self.root, index);
+        link.take().map(|node| {
+            *link = node.next;
+            self.len -= 1;
+            node.elem
+        })
+    }
+}
+
+impl <T> List<T> {
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.iter().nth(index)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        List::walk_link(&mut self.root, index).as_mut().map(|node| &mut node.elem)
+    }
+}
+
+/*
+    A view into one position of the list, like the map entry API.
+    An index below len is occupied. An index of exactly len is vacant, because that is the only free slot where an element can be added.
+    Both variants keep the link they found, so filling a slot or removing its element needs no second walk.
+*/
+pub enum Entry<'a, T> {
+    Occupied(OccupiedEntry<'a, T>),
+    Vacant(VacantEntry<'a, T>),
+}
+
+pub struct OccupiedEntry<'a, T> {
+    // always Some
+    link: &'a mut Link<T>,
+    len: &'a mut usize,
+}
+
+pub struct VacantEntry<'a, T> {
+    // always None: the end of the list
+    link: &'a mut Link<T>,
+    len: &'a mut usize,
+}
+
+impl <T> List<T> {
+    // Panics if index > len, like insert().
+    pub fn entry(&mut self, index: usize) -> Entry<'_, T> {
+        assert!(index <= self.len, "entry index (is {}) should be <= len (is {})", index, self.len);
+        let link = List::walk_link(&mut self.root, index);
+        let len = &mut self.len;
+        if link.is_some() {
+            Entry::Occupied(OccupiedEntry { link, len })
+        } else {
+            Entry::Vacant(VacantEntry { link, len })
+        }
+    }
+}
+
+impl <'a, T> Entry<'a, T> {
+    pub fn or_insert(self, default: T) -> &'a mut T {
+        self.or_insert_with(|| default)
+    }
+
+    // default only runs if the slot is vacant
+    pub fn or_insert_with<F: FnOnce() -> T>(self, default: F) -> &'a mut T {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    pub fn and_modify<F: FnOnce(&mut T)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl <'a, T> OccupiedEntry<'a, T> {
+    pub fn get(&self) -> &T {
+        &self.link.as_ref().unwrap().elem
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.link.as_mut().unwrap().elem
+    }
+
+    pub fn into_mut(self) -> &'a mut T {
+        &mut self.link.as_mut().unwrap().elem
+    }
+
+    // Unlinks the node, shifting everything after it forward by one.
+    pub fn remove(self) -> T {
+        let node = self.link.take().unwrap();
+        *self.link = node.next;
+        *self.len -= 1;
+        node.elem
+    }
+}
+
+impl <'a, T> VacantEntry<'a, T> {
+    pub fn insert(self, elem: T) -> &'a mut T {
+        *self.len += 1;
+        &mut self.link.insert(Node::new(elem, None)).elem
+    }
+}
+
+impl <T> List<T> {
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|elem| elem == value)
+    }
+}
+
+impl <T> List<T> {
+    // Reverses the list in place by relinking every node, O(n) and no allocation.
+    pub fn reverse(&mut self) {
+        self.root = List::reverse_link(self.root.take());
+    }
+}
+
+impl <T> List<T> {
+    // Keeps only the elements the predicate accepts, unlinking the rest in a single pass.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        self.retain_mut(|elem| f(elem));
+    }
+
+    pub fn retain_mut<F: FnMut(&mut T) -> bool>(&mut self, mut f: F) {
+        let mut cur = &mut self.root;
+        /*
+            Each node is taken out of its link: a kept node is put back and we step past it,
+            a rejected node has its next moved into the link instead and is dropped.
+        */
+        while let Some(mut node) = cur.take() {
+            if f(&mut node.elem) {
+                cur = &mut cur.insert(node).next;
+            } else {
+                *cur = node.next.take();
+                self.len -= 1;
+            }
+        }
+    }
+}
+
+/*
+    Unlike IntoIter, Drain only borrows the list, so the container can be reused once the Drain is gone.
+    The drained range is detached from the chain up front and its elements are popped lazily as the iterator advances.
+*/
+pub struct Drain<'a, T> {
+    list: &'a mut List<T>,
+    segment: List<T>,
+    // everything after the range, linked back onto list when the Drain is dropped
+    after: List<T>,
+}
+
+impl <T> List<T> {
+    /*
+        Removes the given range of elements and yields them, like Vec::drain; drain(..) empties the whole list.
+        The segment is cut out with two splits and the nodes after it are reconnected on drop.
+        Panics if the start is past the end or the end is past the length of the list.
+    */
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T> {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.len,
+        };
+        assert!(start <= end, "drain start (is {}) should be <= end (is {})", start, end);
+        assert!(end <= self.len, "drain end (is {}) should be <= len (is {})", end, self.len);
+        let mut segment = self.split_off(start);
+        let after = segment.split_off(end - start);
+        Drain { list: self, segment, after }
+    }
+}
+
+impl <'a, T> Iterator for Drain<'a, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.segment.pop()
+    }
+}
+
+impl <'a, T> Drop for Drain<'a, T> {
+    // whatever wasn't yielded is dropped with the segment, so the range is always removed
+    fn drop(&mut self) {
+        self.segment.clear();
+        if !self.after.is_empty() {
+            self.list.append(&mut self.after);
+        }
+    }
+}
+
+/*
+    Walks the list with a cursor on the link it is about to look at.
+    Matching nodes are unlinked and yielded; the others stay where they are.
+    Dropping the iterator early leaves the unvisited part of the list untouched.
+*/
+pub struct ExtractIf<'a, T, F> {
+    // None once the end of the list has been reached
+    cur: Option<&'a mut Link<T>>,
+    len: &'a mut usize,
+    pred: F,
+}
+
+impl <T> List<T> {
+    pub fn extract_if<F: FnMut(&mut T) -> bool>(&mut self, pred: F) -> ExtractIf<'_, T, F> {
+        // borrowing root and len separately lets the iterator relink nodes and fix up the count
+        ExtractIf { cur: Some(&mut self.root), len: &mut self.len, pred }
+    }
+}
+
+impl <'a, T, F: FnMut(&mut T) -> bool> Iterator for ExtractIf<'a, T, F> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let link = self.cur.take()?;
+            let mut node = link.take()?;
+            if (self.pred)(&mut node.elem) {
+                *link = node.next.take();
+                *self.len -= 1;
+                self.cur = Some(link);
+                return Some(node.elem);
+            }
+            self.cur = Some(&mut link.insert(node).next);
+        }
+    }
+}
+
+impl <T> List<T> {
+    /*
+        Keeps the first len elements and drops the rest. The cut-off chain is wrapped in a List
+        so it goes through the same iterative Drop instead of recursing. No effect if len >= self.len().
+    */
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len {
+            drop(self.split_off(len));
+        }
+    }
+}
+
+impl <T> List<T> {
+    /*
+        The list only owns its nodes through Box, so there is no safe way to keep a second pointer at the tail.
+        These walk the chain instead, O(n).
+    */
+    pub fn last(&self) -> Option<&T> {
+        self.iter().last()
+    }
+
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        self.iter_mut().last()
+    }
+}
+
+impl <T> List<T> {
+    /*
+        Swaps the elements at i and j, the nodes stay where they are. Panics if either index is out of bounds.
+        Every &mut T an IterMut yields is disjoint from the others, so one walk can hand out both references.
+    */
+    pub fn swap(&mut self, i: usize, j: usize) {
+        assert!(i < self.len && j < self.len, "swap indices ({}, {}) out of bounds for len {}", i, j, self.len);
+        if i == j {
+            return;
+        }
+        let (lo, hi) = if i < j { (i, j) } else { (j, i) };
+        let mut iter = self.iter_mut();
+        let a = iter.nth(lo).unwrap();
+        let b = iter.nth(hi - lo - 1).unwrap();
+        mem::swap(a, b);
+    }
+}
+
+impl <T> List<T> {
+    /*
+        Moves the first n nodes to the back by relinking, like VecDeque::rotate_left. Panics if n > len.
+        Only three next pointers change: the cut after n, the root, and the old tail which now points at the old head.
+    */
+    pub fn rotate_left(&mut self, n: usize) {
+        assert!(n <= self.len, "cannot rotate by more than the length of the list");
+        if n == 0 || n == self.len {
+            return;
+        }
+        let back = List::split_link(&mut self.root, n);
+        let front = mem::replace(&mut self.root, back);
+        *List::walk_link(&mut self.root, self.len - n) = front;
+    }
+
+    // Moves the last n nodes to the front. Panics if n > len.
+    pub fn rotate_right(&mut self, n: usize) {
+        assert!(n <= self.len, "cannot rotate by more than the length of the list");
+        self.rotate_left(self.len - n);
+    }
+}
+
+impl <T> List<T> {
+    // Removes consecutive repeated elements, keeping the first of each run, like Vec::dedup.
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut T) -> K>(&mut self, mut key: F) {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    /*
+        Same argument order as Vec::dedup_by: same_bucket(a, b) is called with a the node after b,
+        and a is unlinked if it returns true. One pass, the removed nodes are dropped as we go.
+    */
+    pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, mut same_bucket: F) {
+        let mut cur = self.root.as_deref_mut();
+        while let Some(node) = cur {
+            while let Some(mut next) = node.next.take() {
+                if same_bucket(&mut next.elem, &mut node.elem) {
+                    node.next = next.next.take();
+                    self.len -= 1;
+                } else {
+                    node.next = Some(next);
+                    break;
+                }
+            }
+            cur = node.next.as_deref_mut();
+        }
+    }
+}
+
+impl <T> List<T> {
+    /*
+        Bottom-up merge sort: each pass walks the chain, cuts off pairs of sorted runs of width nodes,
+        merges them and links the result back in, then doubles width.
+        Nodes are only relinked, never reallocated, and merge_links is stable so equal elements keep their order.
+    */
+    fn merge_sort<F>(&mut self, cmp: &mut F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut width = 1;
+        while width < self.len {
+            let mut rest = self.root.take();
+            let mut tail = &mut self.root;
+            while rest.is_some() {
+                let mut second = List::split_link(&mut rest, width);
+                let after = List::split_link(&mut second, width);
+                *tail = List::merge_links(rest, second, cmp);
+                tail = List::walk_link(tail, 2 * width);
+                rest = after;
+            }
+            width *= 2;
+        }
+    }
+
+    // Sorts ascending in O(n log n) by relinking nodes. The sort is stable.
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.merge_sort(&mut T::cmp);
+    }
+
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut cmp: F) {
+        self.merge_sort(&mut cmp);
+    }
+
+    pub fn sort_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, mut key: F) {
+        self.merge_sort(&mut |a: &T, b: &T| key(a).cmp(&key(b)));
+    }
+}
+
+impl <T> List<T> {
+    /*
+        Moves every node into one of two lists depending on the predicate: (accepted, rejected).
+        Both results keep the original relative order since nodes are linked in at each list's tail cursor.
+    */
+    pub fn partition<F: FnMut(&T) -> bool>(mut self, mut f: F) -> (List<T>, List<T>) {
+        let mut yes = List::new();
+        let mut no = List::new();
+        let mut yes_tail = &mut yes.root;
+        let mut no_tail = &mut no.root;
+        while let Some(node) = self.pop_node() {
+            if f(&node.elem) {
+                yes_tail = &mut yes_tail.insert(node).next;
+                yes.len += 1;
+            } else {
+                no_tail = &mut no_tail.insert(node).next;
+                no.len += 1;
+            }
+        }
+        (yes, no)
+    }
+}
+
+impl <T> List<T> {
+    /*
+        Consumes the list and builds a List<U> in the same order.
+        The new nodes are linked at a tail cursor, so unlike a pop/push loop the order isn't reversed.
+    */
+    pub fn map<U, F: FnMut(T) -> U>(self, mut f: F) -> List<U> {
+        self.filter_map(|elem| Some(f(elem)))
+    }
+
+    pub fn filter_map<U, F: FnMut(T) -> Option<U>>(mut self, mut f: F) -> List<U> {
+        let mut mapped = List::new();
+        let mut tail = &mut mapped.root;
+        while let Some(elem) = self.pop() {
+            if let Some(elem) = f(elem) {
+                tail = &mut tail.insert(Node::new(elem, Link::None)).next;
+                mapped.len += 1;
+            }
+        }
+        mapped
+    }
+}
+
+impl <T> List<T> {
+    // Consuming retain: the kept nodes are reused as they are, in the same order.
+    pub fn filter<F: FnMut(&T) -> bool>(mut self, f: F) -> List<T> {
+        self.retain(f);
+        self
+    }
+}
+
+impl <A, B> List<(A, B)> {
+    // Pairs up the elements of two lists in order, stopping at the shorter one. Leftovers are dropped.
+    pub fn zip(mut a: List<A>, mut b: List<B>) -> List<(A, B)> {
+        let mut zipped = List::new();
+        let mut tail = &mut zipped.root;
+        while let (Some(x), Some(y)) = (a.pop(), b.pop()) {
+            tail = &mut tail.insert(Node::new((x, y), Link::None)).next;
+            zipped.len += 1;
+        }
+        zipped
+    }
+
+    // The inverse of zip, splitting each pair in a single pass and keeping the order in both lists.
+    pub fn unzip(mut self) -> (List<A>, List<B>) {
+        let len = self.len;
+        let mut left = List::new();
+        let mut right = List::new();
+        let mut left_tail = &mut left.root;
+        let mut right_tail = &mut right.root;
+        while let Some((x, y)) = self.pop() {
+            left_tail = &mut left_tail.insert(Node::new(x, Link::None)).next;
+            right_tail = &mut right_tail.insert(Node::new(y, Link::None)).next;
+        }
+        left.len = len;
+        right.len = len;
+        (left, right)
+    }
+}
+
+impl <T> List<List<T>> {
+    /*
+        Splices the inner chains one after another. The inner nodes are relinked, not popped and pushed again,
+        so the only walk is along each inner chain to find where the next one attaches.
+    */
+    pub fn flatten(mut self) -> List<T> {
+        let mut flat = List::new();
+        let mut tail = &mut flat.root;
+        while let Some(mut inner) = self.pop() {
+            *tail = inner.root.take();
+            tail = List::walk_link(tail, inner.len);
+            flat.len += inner.len;
+            inner.len = 0;
+        }
+        flat
+    }
+}
+
+impl <T> List<T> {
+    /*
+        Splits the chain in front of the first element matching f and returns the tail starting at that element.
+        Without a match self is left whole and the returned list is empty.
+    */
+    pub fn split_when<F: FnMut(&T) -> bool>(&mut self, mut f: F) -> List<T> {
+        let mut kept = 0;
+        let link = self.find_link(|elem| {
+            let hit = f(elem);
+            if !hit {
+                kept += 1;
+            }
+            hit
+        });
+        let root = link.take();
+        let len = self.len - kept;
+        self.len = kept;
+        List { root, len }
+    }
+}
+
+impl <T> List<T> {
+    /*
+        Links other's nodes in front of self's, so other's head becomes the new head.
+        This can't be done in constant time: a singly-linked Box list keeps no tail pointer, so the last node of other
+        has to be walked to before self's chain can hang off it, O(len of other).
+        self's nodes are never visited though, so prepending short lists onto a long one stays cheap.
+    */
+    pub fn prepend(&mut self, mut other: List<T>) {
+        mem::swap(self, &mut other);
+        self.append(&mut other);
+    }
+}
+
+impl <T> List<T> {
+    // Pops the head only if the predicate approves of it, so peeking and popping can't drift apart.
+    pub fn pop_if<F: FnOnce(&mut T) -> bool>(&mut self, f: F) -> Option<T> {
+        if f(self.peek_mut()?) {
+            self.pop()
+        } else {
+            None
+        }
+    }
+}
+
+/*
+    Both keep the source order: the first item ends up closest to the head.
+    Repeated push() would reverse it, so the items are linked at a tail cursor instead.
+*/
+impl <T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = List::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl <T> Extend<T> for List<T> {
+    // Appends at the back like LinkedList::extend, so an existing list is walked once to find its tail.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut tail = List::walk_link(&mut self.root, self.len);
+        for elem in iter {
+            tail = &mut tail.insert(Node::new(elem, Link::None)).next;
+            self.len += 1;
+        }
+    }
+}
+
+impl <T: Clone> Clone for List<T> {
+    /*
+        A derived Clone would clone the Box in next, which clones its node, which clones its next...
+        one stack frame per node. Collecting from iter() copies node by node in a loop and keeps the order.
+    */
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+
+    // Overwrites the elements of the existing nodes in place, then only allocates or drops the difference in length.
+    fn clone_from(&mut self, source: &Self) {
+        let mut theirs = source.iter();
+        let mut reused = 0;
+        for (ours, elem) in self.iter_mut().zip(&mut theirs) {
+            ours.clone_from(elem);
+            reused += 1;
+        }
+        if reused < self.len {
+            self.truncate(reused);
+        } else {
+            self.extend(theirs.cloned());
+        }
+    }
+}
+
+/*
+    All of these print the elements that are (still) reachable in head order, e.g. [3, 2, 1].
+    The iterators only show what they have left to yield.
+*/
+impl <T: fmt::Debug> fmt::Debug for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl <T: fmt::Debug> fmt::Debug for IntoIter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0.iter()).entries(self.1.iter()).finish()
+    }
+}
+
+impl <'a, T: fmt::Debug> fmt::Debug for Iter<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Option<&Node> is Copy, so a fresh Iter can walk the rest without disturbing this one
+        f.debug_list()
+            .entries(self.buffered.iter())
+            .entries(Iter { next: self.next, len: self.len, buffered: VecDeque::new() })
+            .finish()
+    }
+}
+
+impl <'a, T: fmt::Debug> fmt::Debug for IterMut<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.buffered.iter())
+            .entries(Iter { next: self.next.as_deref(), len: self.len, buffered: VecDeque::new() })
+            .finish()
+    }
+}
+
+// Display adapter returned by display_with, printing the elements joined by a custom separator.
+pub struct DisplayWith<'a, T> {
+    list: &'a List<T>,
+    separator: &'a str,
+}
+
+impl <T> List<T> {
+    pub fn display_with<'a>(&'a self, separator: &'a str) -> DisplayWith<'a, T> {
+        DisplayWith { list: self, separator }
+    }
+}
+
+impl <'a, T: fmt::Display> fmt::Display for DisplayWith<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, elem) in self.list.iter().enumerate() {
+            if i > 0 {
+                f.write_str(self.separator)?;
+            }
+            write!(f, "{}", elem)?;
+        }
+        Ok(())
+    }
+}
+
+// Prints the chain head first as `3 -> 2 -> 1`; an empty list prints nothing.
+impl <T: fmt::Display> fmt::Display for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display_with(" -> ").fmt(f)
+    }
+}
+
+// Element-wise comparison; the cached lengths let lists of different sizes bail out before any walk.
+impl <T: PartialEq> PartialEq for List<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl <T: Eq> Eq for List<T> {}
+
+impl <T: PartialEq> PartialEq<Vec<T>> for List<T> {
+    fn eq(&self, other: &Vec<T>) -> bool {
+        self.len == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl <T: PartialEq> PartialEq<&[T]> for List<T> {
+    fn eq(&self, other: &&[T]) -> bool {
+        self.len == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+// Lexicographic from the head, the same order Vec and LinkedList use.
+impl <T: PartialOrd> PartialOrd for List<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl <T: Ord> Ord for List<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+// Hashes the length first, so lists that concatenate to the same elements (e.g. [[1], [2]] and [[1, 2]]) hash differently.
+impl <T: Hash> Hash for List<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len);
+        for elem in self.iter() {
+            elem.hash(state);
+        }
+    }
+}
+
+// Panics on an out of bounds index like Vec. Each access walks from the head, so list[i] is O(i).
+impl <T> Index<usize> for List<T> {
+    type Output = T;
+    fn index(&self, index: usize) -> &T {
+        let len = self.len;
+        self.get(index)
+            .unwrap_or_else(|| panic!("index out of bounds: the len is {} but the index is {}", len, index))
+    }
+}
+
+impl <T> IndexMut<usize> for List<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len;
+        self.get_mut(index)
+            .unwrap_or_else(|| panic!("index out of bounds: the len is {} but the index is {}", len, index))
+    }
+}
+
+/*
+    A singly-linked chain can only be walked forwards, so none of the iterators can step back a node at a time.
+    Instead the first next_back moves whatever is left into a VecDeque once, O(n), and both ends pop from that:
+        Iter     - buffers the element references of the remaining len nodes (the chain may go on past them)
+        IterMut  - the same, which also avoids walking again and aliasing the &mut it already handed out
+        IntoIter - moves the remaining elements themselves out of the list
+    That makes .rev() and mixed front/back iteration amortised O(1) per element, at the cost of O(n) extra memory.
+    into_iter_back and rev_in_place reverse by relinking instead and need no buffer.
+*/
+impl <'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while self.len > 0 {
+            let node = self.next?;
+            self.buffered.push_back(&node.elem);
+            self.next = node.next.as_deref();
+            self.len -= 1;
+        }
+        self.buffered.pop_back()
+    }
+}
+
+impl <'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let mut cur = self.next.take();
+        while let Some(Node { elem, next, .. }) = cur {
+            self.buffered.push_back(elem);
+            cur = next.as_deref_mut();
+        }
+        self.len = 0;
+        self.buffered.pop_back()
+    }
+}
+
+impl <T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while let Some(elem) = self.0.pop() {
+            self.1.push_back(elem);
+        }
+        self.1.pop_back()
+    }
+}
+
+// Every iterator knows exactly how much is left thanks to the cached length, see the size_hint impls above.
+impl <'a, T> ExactSizeIterator for Iter<'a, T> {}
+impl <'a, T> ExactSizeIterator for IterMut<'a, T> {}
+impl <T> ExactSizeIterator for IntoIter<T> {}
+
+/*
+    All three keep returning None once exhausted, with the buffer empty as well:
+        Iter     - exhaustion comes from the len counter reaching 0; next may still be Some, e.g. after next_back
+                   on an Iter from split, whose chain runs on into the next piece
+        IterMut  - next is None and len is 0
+        IntoIter - the list is empty
+*/
+impl <'a, T> FusedIterator for Iter<'a, T> {}
+impl <'a, T> FusedIterator for IterMut<'a, T> {}
+impl <T> FusedIterator for IntoIter<T> {}
+
+// Yields every pair of adjacent elements, like slice::windows(2) but borrowing straight from the nodes.
+pub struct Pairs<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl <T> List<T> {
+    pub fn iter_pairs(&self) -> Pairs<'_, T> {
+        Pairs { next: self.root.as_deref() }
+    }
+}
+
+impl <'a, T> Iterator for Pairs<'a, T> {
+    type Item = (&'a T, &'a T);
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.next?;
+        let second = first.next.as_deref()?;
+        self.next = Some(second);
+        Some((&first.elem, &second.elem))
+    }
+}
+
+pub struct IntoChunks<T> {
+    rest: List<T>,
+    size: usize,
+}
+
+impl <T> List<T> {
+    /*
+        Splits the list into lists of n elements from the head, the last one possibly shorter.
+        Each chunk is cut off lazily with split_off, so no element is moved or copied. Panics if n is 0.
+    */
+    pub fn into_chunks(self, n: usize) -> IntoChunks<T> {
+        assert!(n != 0, "chunk size must be non-zero");
+        IntoChunks { rest: self, size: n }
+    }
+}
+
+impl <T> Iterator for IntoChunks<T> {
+    type Item = List<T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let tail = self.rest.split_off(self.size.min(self.rest.len));
+        Some(mem::replace(&mut self.rest, tail))
+    }
+}
+
+/*
+    Yields the runs between elements matching the predicate, with the same rules as slice::split:
+    separators themselves are never yielded, adjacent separators give an empty run in between,
+    and n separators always give n + 1 runs (so an empty list yields one empty run).
+*/
+pub struct Split<'a, T, F> {
+    next: Option<&'a Node<T>>,
+    finished: bool,
+    pred: F,
+}
+
+pub struct IntoSplit<T, F> {
+    rest: List<T>,
+    finished: bool,
+    pred: F,
+}
+
+impl <T> List<T> {
+    pub fn split<F: FnMut(&T) -> bool>(&self, pred: F) -> Split<'_, T, F> {
+        Split { next: self.root.as_deref(), finished: false, pred }
+    }
+
+    // The owning version: each run is cut off the chain as its own List and the separator nodes are dropped.
+    pub fn into_split<F: FnMut(&T) -> bool>(self, pred: F) -> IntoSplit<T, F> {
+        IntoSplit { rest: self, finished: false, pred }
+    }
+}
+
+impl <'a, T, F: FnMut(&T) -> bool> Iterator for Split<'a, T, F> {
+    type Item = Iter<'a, T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        // each run is handed out as an Iter bounded by the number of nodes before the separator
+        let start = self.next;
+        let mut len = 0;
+        let mut cur = self.next;
+        while let Some(node) = cur {
+            if (self.pred)(&node.elem) {
+                self.next = node.next.as_deref();
+                return Some(Iter { next: start, len, buffered: VecDeque::new() });
+            }
+            len += 1;
+            cur = node.next.as_deref();
+        }
+        self.finished = true;
+        Some(Iter { next: start, len, buffered: VecDeque::new() })
+    }
+}
+
+impl <T, F: FnMut(&T) -> bool> Iterator for IntoSplit<T, F> {
+    type Item = List<T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let mut tail = self.rest.split_when(&mut self.pred);
+        if tail.is_empty() {
+            self.finished = true;
+            return Some(mem::take(&mut self.rest));
+        }
+        // tail starts at the separator
+        tail.pop();
+        Some(mem::replace(&mut self.rest, tail))
+    }
+}
+
+/*
+    Identifies one node of a list by the id it was stamped with when it was allocated.
+    A heap address would not do: once a node is removed its allocation may be reused by a new node,
+    and a stale handle would then silently refer to that one. Ids are never handed out twice,
+    so a handle to a removed node just stops matching anything, and handles from a different list never match.
+    A handle follows its node when the node is relinked, e.g. by reverse or sort.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeHandle(u64);
+
+impl NodeHandle {
+    fn of<T>(node: &Node<T>) -> NodeHandle {
+        NodeHandle(node.id)
+    }
+}
+
+pub struct IterHandles<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl <T> List<T> {
+    // Like iter(), but every element comes with a handle to its node for later edits.
+    pub fn iter_handles(&self) -> IterHandles<'_, T> {
+        IterHandles { next: self.root.as_deref() }
+    }
+
+    // the link pointing at the node behind handle, or the empty link at the end if it isn't in this list
+    fn handle_link(&mut self, handle: NodeHandle) -> &mut Link<T> {
+        let mut cur = &mut self.root;
+        while cur.as_deref().is_some_and(|node| NodeHandle::of(node) != handle) {
+            cur = &mut cur.as_mut().unwrap().next;
+        }
+        cur
+    }
+
+    /*
+        Unlinks the node behind the handle and returns its element, or None if the node isn't in the list.
+        This is O(n): unlinking a node means rewriting the next of the node before it,
+        and a singly-linked node has no way to reach its predecessor, so it has to be found from the head.
+        Constant-time unlinking of known nodes (as an LRU needs) takes back-links, like the deque in fourth.rs has.
+    */
+    pub fn remove_by_handle(&mut self, handle: NodeHandle) -> Option<T> {
+        let link = self.handle_link(handle);
+        let node = link.take()?;
+        *link = node.next;
+        self.len -= 1;
+        Some(node.elem)
+    }
+
+    /*
+        Links a new node right after the one behind the handle and returns the new node's handle.
+        If the handle isn't in the list the element is given back as Err.
+    */
+    pub fn insert_after_handle(&mut self, handle: NodeHandle, elem: T) -> Result<NodeHandle, T> {
+        match self.handle_link(handle) {
+            Some(node) => {
+                let next = node.next.take();
+                let inserted = node.next.insert(Node::new(elem, next));
+                let new_handle = NodeHandle::of(inserted);
+                self.len += 1;
+                Ok(new_handle)
+            },
+            None => Err(elem),
+        }
+    }
+}
+
+impl <'a, T> Iterator for IterHandles<'a, T> {
+    type Item = (NodeHandle, &'a T);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            (NodeHandle::of(node), &node.elem)
+        })
+    }
+}
+
+/*
+    A read-only position in the list that, unlike an iterator, can be paused, inspected and asked where it is.
+    It only holds shared references, so it never changes the list, and any number of them can exist at once.
+
+    Like std's LinkedList cursors, the positions form a ring with a "ghost" non-element between the tail and the head:
+    at the ghost current() and index() are None, move_next goes on to the head and move_prev back to the tail.
+    The list doesn't link backwards, so everything that looks at earlier elements walks again from the head.
+*/
+pub struct Cursor<'a, T> {
+    // kept to restart from the head, the only way back in a singly-linked list
+    list: &'a List<T>,
+    current: Option<&'a Node<T>>,
+    // equals list.len at the ghost
+    index: usize,
+}
+
+impl <T> List<T> {
+    // Starts at the head, or at the ghost if the list is empty.
+    pub fn cursor_front(&self) -> Cursor<'_, T> {
+        Cursor { list: self, current: self.root.as_deref(), index: 0 }
+    }
+
+    // Starts at the tail, or at the ghost if the list is empty. O(n).
+    pub fn cursor_back(&self) -> Cursor<'_, T> {
+        let mut cursor = Cursor { list: self, current: None, index: self.len };
+        cursor.move_prev();
+        cursor
+    }
+}
+
+impl <'a, T> Cursor<'a, T> {
+    fn node_at(&self, index: usize) -> Option<&'a Node<T>> {
+        let mut node = self.list.root.as_deref()?;
+        for _ in 0..index {
+            node = node.next.as_deref()?;
+        }
+        Some(node)
+    }
+
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    pub fn current(&self) -> Option<&'a T> {
+        self.current.map(|node| &node.elem)
+    }
+
+    // At the ghost this is the head.
+    pub fn peek_next(&self) -> Option<&'a T> {
+        match self.current {
+            Some(node) => node.next.as_deref().map(|node| &node.elem),
+            None => self.list.peek(),
+        }
+    }
+
+    // At the ghost this is the tail, at the head it is None. O(index).
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        let prev = self.index.checked_sub(1)?;
+        self.node_at(prev).map(|node| &node.elem)
+    }
+
+    // Moves to the next element; from the tail to the ghost, and from the ghost to the head.
+    pub fn move_next(&mut self) {
+        match self.current {
+            Some(node) => {
+                self.current = node.next.as_deref();
+                self.index += 1;
+            },
+            None => {
+                self.current = self.list.root.as_deref();
+                self.index = 0;
+            },
+        }
+    }
+
+    // Moves to the previous element; from the head to the ghost, and from the ghost to the tail. O(index).
+    pub fn move_prev(&mut self) {
+        match self.index.checked_sub(1) {
+            Some(prev) => {
+                self.current = self.node_at(prev);
+                self.index = prev;
+            },
+            None => {
+                self.current = None;
+                self.index = self.list.len;
+            },
+        }
+    }
+
+    // Moves n elements forward, stopping at the ghost rather than wrapping around.
+    pub fn seek_forward(&mut self, n: usize) {
+        for _ in 0..n {
+            if self.current.is_none() {
+                break;
+            }
+            self.move_next();
+        }
+    }
+
+    /*
+        Moves to the given index, or to the ghost if index >= len.
+        Going backwards starts over from the head, so that direction is O(index).
+    */
+    pub fn seek_to(&mut self, index: usize) {
+        if index < self.index {
+            self.current = self.list.root.as_deref();
+            self.index = 0;
+        }
+        self.seek_forward(index - self.index);
+    }
+}
+
+/*
+    Copying a cursor gives a second walker that starts at the same position and then moves independently, for two-pointer walks.
+    Derive would require T: Clone, but only the references are copied.
+*/
+impl <'a, T> Clone for Cursor<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl <'a, T> Copy for Cursor<'a, T> {}
+
+/*
+    Cursors are equal when they point at the same position of the same list (compared by address, not by contents).
+    Cursors into the same list are ordered by index, and the ghost counts as the position after the tail.
+    Cursors into different lists can't be ordered: partial_cmp returns None.
+*/
+impl <'a, T> PartialEq for Cursor<'a, T> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.list, other.list) && self.index == other.index
+    }
+}
+
+impl <'a, T> Eq for Cursor<'a, T> {}
+
+impl <'a, T> PartialOrd for Cursor<'a, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        std::ptr::eq(self.list, other.list).then(|| self.index.cmp(&other.index))
+    }
+}
+
+/*
+    A cursor that can edit the list where it stands.
+    Holding a &mut to a node deep in the chain would lock up everything before it, so instead the cursor unzips the list:
+    the nodes it has walked past are moved onto prefix, nearest first, and list.root is the current node.
+    Stepping either way moves one node between the fronts of list.root and prefix, so every edit
+    around the cursor is O(1). When the cursor is dropped, prefix is zipped back on, O(index).
+    While nodes are parked on prefix, list.len doesn't count them (like Vec::drain shortening the Vec up front):
+    if the cursor is leaked with mem::forget, the parked nodes leak too, but the list is still consistent.
+
+    The ghost non-element works like in Cursor and std's CursorMut: it is where list.root is empty.
+    Inserting after the ghost puts the element at the front, inserting before it puts it at the back.
+    The two moves that wrap around the ghost (move_next from the ghost, move_prev from the head)
+    have to zip or unzip the whole list, O(n).
+*/
+pub struct CursorMut<'a, T> {
+    list: &'a mut List<T>,
+    // the nodes before the cursor in reverse order, not counted in list.len
+    prefix: Link<T>,
+    // also the number of nodes on prefix
+    index: usize,
+}
+
+impl <T> List<T> {
+    // Starts at the head, or at the ghost if the list is empty.
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut { list: self, prefix: Link::None, index: 0 }
+    }
+
+    // Starts at the tail, or at the ghost if the list is empty. O(n).
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        let mut cursor = self.cursor_front_mut();
+        while cursor.step_forward() {}
+        cursor.move_prev();
+        cursor
+    }
+}
+
+impl <'a, T> CursorMut<'a, T> {
+    // moves the current node onto prefix, false at the ghost
+    fn step_forward(&mut self) -> bool {
+        let Some(mut node) = self.list.root.take() else { return false };
+        self.list.root = node.next.take();
+        node.next = self.prefix.take();
+        self.prefix = Some(node);
+        self.index += 1;
+        self.list.len -= 1;
+        true
+    }
+
+    // moves the nearest node of prefix back to the front of list.root, false at the head
+    fn step_back(&mut self) -> bool {
+        let Some(mut node) = self.prefix.take() else { return false };
+        self.prefix = node.next.take();
+        node.next = self.list.root.take();
+        self.list.root = Some(node);
+        self.index -= 1;
+        self.list.len += 1;
+        true
+    }
+
+    pub fn index(&self) -> Option<usize> {
+        self.list.root.as_ref().map(|_| self.index)
+    }
+
+    // Same as std: the current element can be changed in place.
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.list.root.as_mut().map(|node| &mut node.elem)
+    }
+
+    pub fn current_mut(&mut self) -> Option<&mut T> {
+        self.current()
+    }
+
+    // At the ghost this is the head, the bottom of prefix, O(n).
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        match self.list.root.as_mut() {
+            Some(node) => node.next.as_mut().map(|node| &mut node.elem),
+            None => {
+                let head = self.index.checked_sub(1)?;
+                List::walk_link(&mut self.prefix, head).as_mut().map(|node| &mut node.elem)
+            },
+        }
+    }
+
+    // At the ghost this is the tail, at the head it is None.
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        self.prefix.as_mut().map(|node| &mut node.elem)
+    }
+
+    // Moves to the next element; from the tail to the ghost, and from the ghost to the head.
+    pub fn move_next(&mut self) {
+        if !self.step_forward() {
+            while self.step_back() {}
+        }
+    }
+
+    // Moves to the previous element; from the head to the ghost, and from the ghost to the tail.
+    pub fn move_prev(&mut self) {
+        if self.list.root.is_some() && self.prefix.is_none() {
+            while self.step_forward() {}
+        } else {
+            self.step_back();
+        }
+    }
+
+    // Moves n elements forward, stopping at the ghost rather than wrapping around.
+    pub fn seek_forward(&mut self, n: usize) {
+        for _ in 0..n {
+            if !self.step_forward() {
+                break;
+            }
+        }
+    }
+
+    /*
+        Moves to the given index, or to the ghost if index >= len.
+        Going backwards hands nodes from prefix back to list.root, so both directions only cost the distance moved.
+    */
+    pub fn seek_to(&mut self, index: usize) {
+        while self.index > index && self.step_back() {}
+        self.seek_forward(index - self.index);
+    }
+
+    // Inserts right before the current element, the cursor stays on the current element.
+    pub fn insert_before(&mut self, elem: T) {
+        self.prefix = Some(Node::new(elem, self.prefix.take()));
+        self.index += 1;
+    }
+
+    // Inserts right after the current element, the cursor doesn't move.
+    pub fn insert_after(&mut self, elem: T) {
+        match self.list.root.as_mut() {
+            Some(node) => {
+                node.next = Some(Node::new(elem, node.next.take()));
+                self.list.len += 1;
+            },
+            None => {
+                // at the ghost, so the new element becomes the head: the bottom of prefix
+                *List::walk_link(&mut self.prefix, self.index) = Some(Node::new(elem, Link::None));
+                self.index += 1;
+            },
+        }
+    }
+
+    // Unlinks the current element and returns it; the cursor moves on to the element after it. None at the ghost.
+    pub fn remove_current(&mut self) -> Option<T> {
+        self.list.pop()
+    }
+
+    /*
+        Links all of other in between the current element and the next one, after the ghost at the front of the list.
+        This is O(len of other), not O(1): a singly-linked Box list keeps no tail pointer, so the far end of other
+        has to be walked to before anything can hang off it. The rest of self isn't visited, except at the ghost,
+        where the walked-past prefix is stored back to front and has to be walked to its end as well.
+    */
+    pub fn splice_after(&mut self, mut other: List<T>) {
+        let len = mem::take(&mut other.len);
+        let mut chain = other.root.take();
+        match self.list.root.as_mut() {
+            Some(node) => {
+                *List::walk_link(&mut chain, len) = node.next.take();
+                node.next = chain;
+                self.list.len += len;
+            },
+            None => {
+                // prefix is stored back to front, so the spliced chain has to go on reversed
+                *List::walk_link(&mut self.prefix, self.index) = List::reverse_link(chain);
+                self.index += len;
+            },
+        }
+    }
+
+    /*
+        Links all of other in right before the current element, before the ghost at the back.
+        O(len of other) rather than O(1) for the same reason as splice_after: the nodes go onto the reversed prefix one by one.
+    */
+    pub fn splice_before(&mut self, mut other: List<T>) {
+        while let Some(mut node) = other.pop_node() {
+            node.next = self.prefix.take();
+            self.prefix = Some(node);
+            self.index += 1;
+        }
+    }
+
+    /*
+        Cuts off everything after the current element and returns it, O(1).
+        At the ghost, the whole list is moved out instead (like std).
+    */
+    pub fn split_after(&mut self) -> List<T> {
+        match self.list.root.as_mut() {
+            Some(node) => {
+                // list.len counts the current node and everything after it
+                let len = self.list.len - 1;
+                self.list.len = 1;
+                List { root: node.next.take(), len }
+            },
+            None => self.split_before(),
+        }
+    }
+
+    /*
+        Cuts off everything before the current element and returns it; the cursor ends up at index 0.
+        At the ghost that is the whole list. prefix is stored back to front so it has to be reversed into a list, O(index).
+    */
+    pub fn split_before(&mut self) -> List<T> {
+        // prefix isn't counted in list.len, so only index changes
+        let len = mem::take(&mut self.index);
+        List { root: List::reverse_link(self.prefix.take()), len }
+    }
+}
+
+impl <'a, T> Drop for CursorMut<'a, T> {
+    fn drop(&mut self) {
+        while self.step_back() {}
+    }
+}
+
+/*
+    The same bottom-up merge sort as sort(), but built only from CursorMut moves and run one pass per next() call.
+    It is a teaching aid: after each pass you can look at the list through list() and check which nodes moved.
+    A pass cuts the list into runs of width elements. It then merges each pair of runs by walking a cursor along the left run
+    and splicing every node of the right run in front of the first left element that is bigger than it.
+    Only those nodes are reported as relinked. Once the left run runs out, the rest of the right run is already in place.
+*/
+#[cfg(feature = "sort-steps")]
+pub struct SortSteps<'a, T> {
+    list: &'a mut List<T>,
+    width: usize,
+}
+
+#[cfg(feature = "sort-steps")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortStep {
+    width: usize,
+    relinked: Vec<NodeHandle>,
+}
+
+#[cfg(feature = "sort-steps")]
+impl SortStep {
+    // the length of the runs that were merged in this pass
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    // the nodes that were moved ahead of a node from the run before them, in the order they were moved
+    pub fn relinked(&self) -> &[NodeHandle] {
+        &self.relinked
+    }
+}
+
+#[cfg(feature = "sort-steps")]
+impl <T: Ord> List<T> {
+    pub fn sort_steps(&mut self) -> SortSteps<'_, T> {
+        SortSteps { list: self, width: 1 }
+    }
+}
+
+#[cfg(feature = "sort-steps")]
+impl <'a, T: Ord> SortSteps<'a, T> {
+    // the list as it is between passes
+    pub fn list(&self) -> &List<T> {
+        self.list
+    }
+
+    // merges right into left, pushing the handle of every node that overtakes a node of left
+    fn merge_runs(left: &mut List<T>, mut right: List<T>, relinked: &mut Vec<NodeHandle>) {
+        let mut cursor = left.cursor_front_mut();
+        while let Some(node) = right.pop_node() {
+            // <= keeps equal elements of left first, so the sort stays stable
+            while cursor.current().is_some_and(|elem| *elem <= node.elem) {
+                cursor.move_next();
+            }
+            if cursor.current().is_none() {
+                // at the ghost: everything left in right is bigger than all of left
+                cursor.splice_before(List { root: Some(node), len: 1 });
+                cursor.splice_before(right);
+                return;
+            }
+            relinked.push(NodeHandle::of(&node));
+            cursor.splice_before(List { root: Some(node), len: 1 });
+        }
+    }
+}
+
+#[cfg(feature = "sort-steps")]
+impl <'a, T: Ord> Iterator for SortSteps<'a, T> {
+    type Item = SortStep;
+    fn next(&mut self) -> Option<Self::Item> {
+        let width = self.width;
+        if width >= self.list.len {
+            return None;
+        }
+        let mut rest = mem::take(self.list);
+        let mut relinked = Vec::new();
+        let mut out = self.list.cursor_front_mut();
+        while !rest.is_empty() {
+            let mut cursor = rest.cursor_front_mut();
+            cursor.seek_to(width);
+            let mut left = cursor.split_before();
+            cursor.seek_to(width);
+            let right = cursor.split_before();
+            drop(cursor);
+            SortSteps::merge_runs(&mut left, right, &mut relinked);
+            // out stays on the ghost, so this appends
+            out.splice_before(left);
+        }
+        drop(out);
+        self.width *= 2;
+        Some(SortStep { width, relinked })
+    }
+}
+
+/*
+    Bridges to the chapter-one list in first.rs. The two modules have different Node types, so the nodes themselves can't be handed over,
+    but every element is moved exactly once, straight from its old node into a new one. Nothing is cloned.
+    Both directions keep the order front to back.
+*/
+impl <T> From<crate::first::List<T>> for List<T> {
+    fn from(list: crate::first::List<T>) -> Self {
+        list.into_iter().collect()
+    }
+}
+
+impl <T> From<List<T>> for crate::first::List<T> {
+    fn from(mut list: List<T>) -> Self {
+        // first::List can only push at the front, so the old tail has to go in first
+        list.reverse();
+        let mut first = crate::first::List::new();
+        for elem in list {
+            first.push(elem);
+        }
+        first
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::List;
+    use std::cmp::Ordering;
+    #[test]
+    fn basics() {
+        let mut list = List::new();
+
+        // Check None list behaves right
+        assert_eq!(list.pop(), None);
+
+        // Populate list
+        list.push(1);
+        list.push(2);
+        list.push(3);
+
+        // Check normal removal
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), Some(2));
+
+        // Push some more just to make sure nothing's corrupted
+        list.push(4);
+        list.push(5);
+
+        // Check normal removal
+        assert_eq!(list.pop(), Some(5));
+        assert_eq!(list.pop(), Some(4));
+
+        // Check exhaustion
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), None);
+    }
+
+    #[test]
+    fn peek() {
+        let mut list = List::new();
+        assert_eq!(list.peek(), None);
+        assert_eq!(list.peek_mut(), None);
+        list.push(1); list.push(2); list.push(3);
+
+        assert_eq!(list.peek(), Some(&3));
+        assert_eq!(list.peek_mut(), Some(&mut 3));
+        // list.peek_mut().map(|&mut value| {
+        //     value = 42
+        // });
+        /*
+            The above looks like value should be declared as mutable reference, however, 
+            for closures, it specifies a pattern that will be matched against arguments to the closure.
+            |&mut value| means "the argument is a mutable reference, but just copy it into value"
+        */
+        if let Some(value) = list.peek_mut() {
+            *value = 42
+        }
+
+        assert_eq!(list.peek(), Some(&42));
+        assert_eq!(list.pop(), Some(42));
+    }
+
+    #[test]
+    pub fn into_iter() {
+        let mut list = List::new();
+        list.push(1); list.push(2); list.push(3);
+
+        let mut iter = list.into_iter();
+        assert_eq!(iter.next(), Some(3));
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn iter() {
+        let mut list = List::new();
+        list.push(1); list.push(2); list.push(3);
+
+        let mut iter = list.iter();
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&1));
+    }
+
+    #[test]
+    fn iter_mut() {
+        let mut list = List::new();
+        list.push(1); list.push(2); list.push(3);
+
+        let mut iter = list.iter_mut();
+        assert_eq!(iter.next(), Some(&mut 3));
+        assert_eq!(iter.next(), Some(&mut 2));
+        assert_eq!(iter.next(), Some(&mut 1));
+    }
+
+    #[test]
+    fn reverse_in_groups() {
+        let mut list = List::new();
+        for i in (1..=6).rev() { list.push(i); }
+
+        list.reverse_in_groups(2);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2, 1, 4, 3, 6, 5]);
+
+        let mut list = List::new();
+        for i in (1..=8).rev() { list.push(i); }
+
+        // 8 is not divisible by 3, the trailing [7, 8] stays as is
+        list.reverse_in_groups(3);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![3, 2, 1, 6, 5, 4, 7, 8]);
+
+        list.reverse_in_groups(1);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![3, 2, 1, 6, 5, 4, 7, 8]);
+        list.reverse_in_groups(0);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![3, 2, 1, 6, 5, 4, 7, 8]);
+
+        let mut empty: List<i32> = List::new();
+        empty.reverse_in_groups(2);
+        assert_eq!(empty.pop(), None);
+    }
+
+    // builds a list whose head is the first element of the slice
+    fn list_from(elems: &[i32]) -> List<i32> {
+        elems.iter().copied().collect()
+    }
+
+    fn to_vec(list: &List<i32>) -> Vec<i32> {
+        list.iter().copied().collect()
+    }
+
+    #[test]
+    fn merge() {
+        let merged = list_from(&[1, 4, 6]).merge(list_from(&[2, 3, 5, 7]));
+        assert_eq!(to_vec(&merged), vec![1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(merged.len(), 7);
+
+        let merged = List::new().merge(list_from(&[1, 2]));
+        assert_eq!(to_vec(&merged), vec![1, 2]);
+        let merged = list_from(&[1, 2]).merge(List::new());
+        assert_eq!(to_vec(&merged), vec![1, 2]);
+
+        let merged = list_from(&[1, 2, 2, 5]).merge(list_from(&[2, 5, 5, 8]));
+        assert_eq!(to_vec(&merged), vec![1, 2, 2, 2, 5, 5, 5, 8]);
+    }
+
+    #[test]
+    fn split_iter_mut() {
+        let mut list = list_from(&[1, 2, 3, 4, 5]);
+        let (front, back) = list.split_iter_mut(2);
+        // both halves are alive at the same time
+        let front: Vec<&mut i32> = front.collect();
+        let back: Vec<&mut i32> = back.collect();
+        assert_eq!(front.len(), 2);
+        assert_eq!(back.len(), 3);
+        for value in front { *value += 10; }
+        for value in back { *value += 100; }
+        assert_eq!(to_vec(&list), vec![11, 12, 103, 104, 105]);
+
+        let (front, back) = list.split_iter_mut(0);
+        assert_eq!(front.count(), 0);
+        assert_eq!(back.count(), 5);
+
+        let (front, mut back) = list.split_iter_mut(10);
+        assert_eq!(front.count(), 5);
+        assert_eq!(back.next(), None);
+    }
+
+    #[test]
+    fn swap_heads() {
+        let mut a = list_from(&[1, 2, 3]);
+        let mut b = list_from(&[7, 8]);
+        a.swap_heads(&mut b);
+        assert_eq!(to_vec(&a), vec![7, 2, 3]);
+        assert_eq!(to_vec(&b), vec![1, 8]);
+        assert_eq!((a.len(), b.len()), (3, 2));
+
+        let mut a = list_from(&[1, 2]);
+        let mut b = List::new();
+        a.swap_heads(&mut b);
+        assert_eq!(to_vec(&a), vec![2]);
+        assert_eq!(to_vec(&b), vec![1]);
+        assert_eq!((a.len(), b.len()), (1, 1));
+        b.swap_heads(&mut List::new());
+        assert_eq!(to_vec(&b), vec![]);
+
+        let mut a: List<i32> = List::new();
+        let mut b = List::new();
+        a.swap_heads(&mut b);
+        assert_eq!(a.peek(), None);
+        assert_eq!(b.peek(), None);
+    }
+
+    #[test]
+    fn advance_by() {
+        let mut iter = list_from(&[1, 2, 3, 4]).into_iter();
+        assert_eq!(iter.advance_by(2), Ok(()));
+        assert_eq!(iter.next(), Some(3));
+        assert_eq!(iter.advance_by(0), Ok(()));
+
+        let mut iter = list_from(&[1, 2, 3]).into_iter();
+        assert_eq!(iter.advance_by(3), Ok(()));
+        assert_eq!(iter.next(), None);
+
+        let mut iter = list_from(&[1, 2]).into_iter();
+        assert_eq!(iter.advance_by(5), Err(3));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn extend_front_ordered() {
+        let mut list = list_from(&[9, 10]);
+        list.extend_front_ordered([1, 2, 3]);
+        assert_eq!(to_vec(&list), vec![1, 2, 3, 9, 10]);
+        assert_eq!(list.len(), 5);
+
+        list.extend_front_ordered(Vec::new());
+        assert_eq!(to_vec(&list), vec![1, 2, 3, 9, 10]);
+
+        let mut list = List::new();
+        list.extend_front_ordered(4..7);
+        assert_eq!(to_vec(&list), vec![4, 5, 6]);
+
+        // a panicking iterator leaves the list as it was, len included
+        let mut list = list_from(&[1, 2]);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            list.extend_front_ordered((0..).map(|i| if i == 3 { panic!("iterator failed") } else { i }));
+        }));
+        assert!(result.is_err());
+        assert_eq!(to_vec(&list), vec![1, 2]);
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.iter().len(), 2);
+    }
+
+    #[test]
+    fn remove_value() {
+        let mut list = list_from(&[1, 2, 3, 2, 4]);
+        assert!(list.remove_value(&1));
+        assert_eq!(to_vec(&list), vec![2, 3, 2, 4]);
+        // only the first match goes
+        assert!(list.remove_value(&2));
+        assert_eq!(to_vec(&list), vec![3, 2, 4]);
+        assert!(list.remove_value(&4));
+        assert_eq!(to_vec(&list), vec![3, 2]);
+        assert!(!list.remove_value(&42));
+        assert_eq!(to_vec(&list), vec![3, 2]);
+        assert_eq!(list.len(), 2);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn debug_layout() {
+        let list = list_from(&[1, 2, 3]);
+        let layout = list.debug_layout();
+        assert_eq!(layout.matches(" -> ").count(), 3);
+        assert!(layout.ends_with(", null)"));
+        assert!(layout.contains("(2 @ "));
+
+        assert_eq!(List::<i32>::new().debug_layout(), "[null]");
+    }
+
+    #[test]
+    fn try_from_slice() {
+        let list = List::try_from(&[1, 2, 3][..]).unwrap();
+        assert_eq!(to_vec(&list), vec![1, 2, 3]);
+
+        let too_long = vec![0; super::MAX_SLICE_LEN + 1];
+        let err = List::try_from(&too_long[..]).err().unwrap();
+        assert_eq!(err.slice_len(), super::MAX_SLICE_LEN + 1);
+
+        let at_limit = vec![0; super::MAX_SLICE_LEN];
+        assert!(List::try_from(&at_limit[..]).is_ok());
+    }
+
+    #[test]
+    fn iter_eq() {
+        let list = list_from(&[1, 2, 3]);
+        assert!(list.iter_eq(1..=3));
+        assert!(list.iter_eq([1, 2, 3]));
+        assert!(!list.iter_eq([1, 5, 3]));
+        assert!(!list.iter_eq(1..3));
+        assert!(!list.iter_eq(1..5));
+        assert!(List::new().iter_eq(0..0));
+    }
+
+    #[test]
+    fn to_string_collected() {
+        let mut list = List::new();
+        list.extend_front_ordered("abc".chars());
+        assert_eq!(list.peek(), Some(&'a'));
+        assert_eq!(list.to_string_collected(), "abc");
+        assert_eq!(List::new().to_string_collected(), "");
+    }
+
+    #[test]
+    fn into_rchunks() {
+        let chunks: Vec<Vec<i32>> = list_from(&[1, 2, 3, 4, 5, 6])
+            .into_rchunks(2)
+            .map(|chunk| to_vec(&chunk))
+            .collect();
+        assert_eq!(chunks, vec![vec![5, 6], vec![3, 4], vec![1, 2]]);
+
+        let chunks: Vec<Vec<i32>> = list_from(&[1, 2, 3, 4, 5, 6, 7])
+            .into_rchunks(3)
+            .map(|chunk| to_vec(&chunk))
+            .collect();
+        assert_eq!(chunks, vec![vec![5, 6, 7], vec![2, 3, 4], vec![1]]);
+
+        assert_eq!(List::<i32>::new().into_rchunks(3).count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn into_rchunks_zero() {
+        list_from(&[1]).into_rchunks(0);
+    }
+
+    #[test]
+    fn into_iter_back() {
+        let mut list = List::new();
+        list.push(1); list.push(2); list.push(3);
+        assert_eq!(list.into_iter_back().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        let mut list = List::new();
+        list.push(1); list.push(2); list.push(3);
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn len() {
+        let mut list = List::new();
+        assert_eq!(list.len(), 0);
+        assert!(list.is_empty());
+
+        list.push(1); list.push(2); list.push(3);
+        assert_eq!(list.len(), 3);
+        assert!(!list.is_empty());
+
+        list.pop();
+        assert_eq!(list.len(), 2);
+        list.pop(); list.pop();
+        // popping an empty list must not underflow
+        list.pop();
+        assert_eq!(list.len(), 0);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn clear() {
+        let mut list = list_from(&[1, 2, 3]);
+        list.clear();
+        assert!(list.is_empty());
+        assert_eq!(list.peek(), None);
+
+        // the list is still usable afterwards
+        list.push(4);
+        assert_eq!(to_vec(&list), vec![4]);
+
+        // long lists must not overflow the stack
+        let mut long = List::new();
+        for i in 0..200_000 { long.push(i); }
+        long.clear();
+        assert_eq!(long.len(), 0);
+    }
+
+    #[test]
+    fn append() {
+        let mut list = list_from(&[1, 2]);
+        let mut other = list_from(&[3, 4, 5]);
+        list.append(&mut other);
+        assert_eq!(to_vec(&list), vec![1, 2, 3, 4, 5]);
+        assert_eq!(list.len(), 5);
+        assert!(other.is_empty());
+        assert_eq!(other.peek(), None);
+
+        let mut empty = List::new();
+        empty.append(&mut list);
+        assert_eq!(to_vec(&empty), vec![1, 2, 3, 4, 5]);
+        empty.append(&mut List::new());
+        assert_eq!(empty.len(), 5);
+    }
+
+    #[test]
+    fn split_off() {
+        let mut list = list_from(&[1, 2, 3, 4, 5]);
+        let tail = list.split_off(2);
+        assert_eq!(to_vec(&list), vec![1, 2]);
+        assert_eq!(to_vec(&tail), vec![3, 4, 5]);
+        assert_eq!((list.len(), tail.len()), (2, 3));
+
+        let all = list.split_off(0);
+        assert!(list.is_empty());
+        assert_eq!(to_vec(&all), vec![1, 2]);
+
+        let mut list = list_from(&[1, 2]);
+        let none = list.split_off(2);
+        assert!(none.is_empty());
+        assert_eq!(list.len(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_off_out_of_bounds() {
+        list_from(&[1, 2]).split_off(3);
+    }
+
+    #[test]
+    fn insert() {
+        let mut list = list_from(&[1, 3]);
+        list.insert(1, 2);
+        assert_eq!(to_vec(&list), vec![1, 2, 3]);
+        list.insert(0, 0);
+        assert_eq!(to_vec(&list), vec![0, 1, 2, 3]);
+        list.insert(4, 4);
+        assert_eq!(to_vec(&list), vec![0, 1, 2, 3, 4]);
+        assert_eq!(list.len(), 5);
+
+        let mut empty = List::new();
+        empty.insert(0, 1);
+        assert_eq!(to_vec(&empty), vec![1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_out_of_bounds() {
+        list_from(&[1]).insert(2, 5);
+    }
+
+    #[test]
+    fn remove() {
+        let mut list = list_from(&[1, 2, 3, 4]);
+        assert_eq!(list.remove(1), Some(2));
+        assert_eq!(to_vec(&list), vec![1, 3, 4]);
+        assert_eq!(list.remove(2), Some(4));
+        assert_eq!(list.remove(0), Some(1));
+        assert_eq!(list.remove(1), None);
+        assert_eq!(to_vec(&list), vec![3]);
+        assert_eq!(list.len(), 1);
+    }
+
+    #[test]
+    fn get() {
+        let mut list = list_from(&[1, 2, 3]);
+        assert_eq!(list.get(0), Some(&1));
+        assert_eq!(list.get(2), Some(&3));
+        assert_eq!(list.get(3), None);
+
+        if let Some(value) = list.get_mut(1) {
+            *value = 20;
+        }
+        assert_eq!(list.get_mut(3), None);
+        assert_eq!(to_vec(&list), vec![1, 20, 3]);
+    }
+
+    #[test]
+    fn contains() {
+        let list = list_from(&[1, 2, 3]);
+        assert!(list.contains(&1));
+        assert!(list.contains(&3));
+        assert!(!list.contains(&4));
+        assert!(!List::new().contains(&1));
+    }
+
+    #[test]
+    fn reverse() {
+        let mut list = list_from(&[1, 2, 3]);
+        list.reverse();
+        assert_eq!(to_vec(&list), vec![3, 2, 1]);
+        assert_eq!(list.len(), 3);
+
+        let mut single = list_from(&[1]);
+        single.reverse();
+        assert_eq!(to_vec(&single), vec![1]);
+
+        let mut empty: List<i32> = List::new();
+        empty.reverse();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn retain() {
+        let mut list = list_from(&[1, 2, 3, 4, 5, 6]);
+        list.retain(|&x| x % 2 == 0);
+        assert_eq!(to_vec(&list), vec![2, 4, 6]);
+        assert_eq!(list.len(), 3);
+
+        list.retain(|_| false);
+        assert!(list.is_empty());
+        assert_eq!(list.peek(), None);
+
+        let mut list = list_from(&[1, 2, 3, 4]);
+        list.retain_mut(|x| {
+            *x *= 10;
+            *x != 30
+        });
+        assert_eq!(to_vec(&list), vec![10, 20, 40]);
+        assert_eq!(list.len(), 3);
+    }
+
+    #[test]
+    fn drain() {
+        let mut list = list_from(&[1, 2, 3]);
+        assert_eq!(list.drain(..).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert!(list.is_empty());
+
+        list.push(4); list.push(5); list.push(6);
+        {
+            let mut drain = list.drain(..);
+            assert_eq!(drain.next(), Some(6));
+        }
+        // dropped early, the rest is gone too
+        assert!(list.is_empty());
+        assert_eq!(list.peek(), None);
+
+        list.push(7);
+        assert_eq!(to_vec(&list), vec![7]);
+    }
+
+    #[test]
+    fn drain_range() {
+        let mut list = list_from(&[1, 2, 3, 4, 5, 6]);
+        assert_eq!(list.drain(1..3).collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(list, vec![1, 4, 5, 6]);
+        assert_eq!(list.len(), 4);
+
+        assert_eq!(list.drain(2..).collect::<Vec<_>>(), vec![5, 6]);
+        assert_eq!(list, vec![1, 4]);
+        assert_eq!(list.drain(..=0).next(), Some(1));
+        assert_eq!(list, vec![4]);
+        assert_eq!(list.drain(1..1).count(), 0);
+        assert_eq!(list, vec![4]);
+
+        // the nodes after the range come back even if the Drain is dropped without being used
+        let mut list = list_from(&[1, 2, 3, 4]);
+        list.drain(1..=2);
+        assert_eq!(list, vec![1, 4]);
+        assert_eq!(list.len(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn drain_out_of_bounds() {
+        list_from(&[1, 2]).drain(1..3);
+    }
+
+    #[test]
+    fn extract_if() {
+        let mut list = list_from(&[1, 2, 3, 4, 5, 6]);
+        let evens: Vec<i32> = list.extract_if(|x| *x % 2 == 0).collect();
+        assert_eq!(evens, vec![2, 4, 6]);
+        assert_eq!(to_vec(&list), vec![1, 3, 5]);
+        assert_eq!(list.len(), 3);
+
+        // stopping early keeps everything not yet visited
+        let mut list = list_from(&[1, 2, 3, 4]);
+        assert_eq!(list.extract_if(|x| *x > 1).next(), Some(2));
+        assert_eq!(to_vec(&list), vec![1, 3, 4]);
+        assert_eq!(list.len(), 3);
+
+        let mut iter = list.extract_if(|_| true);
+        assert_eq!(iter.by_ref().count(), 3);
+        assert_eq!(iter.next(), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn truncate() {
+        let mut list = list_from(&[1, 2, 3, 4]);
+        list.truncate(2);
+        assert_eq!(to_vec(&list), vec![1, 2]);
+        assert_eq!(list.len(), 2);
+        list.truncate(5);
+        assert_eq!(to_vec(&list), vec![1, 2]);
+        list.truncate(0);
+        assert!(list.is_empty());
+
+        let mut long = List::new();
+        for i in 0..200_000 { long.push(i); }
+        long.truncate(1);
+        assert_eq!(long.len(), 1);
+    }
+
+    #[test]
+    fn last() {
+        let mut list = list_from(&[1, 2, 3]);
+        assert_eq!(list.last(), Some(&3));
+        if let Some(value) = list.last_mut() {
+            *value = 30;
+        }
+        assert_eq!(to_vec(&list), vec![1, 2, 30]);
+
+        let mut empty: List<i32> = List::new();
+        assert_eq!(empty.last(), None);
+        assert_eq!(empty.last_mut(), None);
+    }
+
+    #[test]
+    fn swap() {
+        let mut list = list_from(&[1, 2, 3, 4]);
+        list.swap(0, 3);
+        assert_eq!(to_vec(&list), vec![4, 2, 3, 1]);
+        list.swap(2, 1);
+        assert_eq!(to_vec(&list), vec![4, 3, 2, 1]);
+        list.swap(1, 1);
+        assert_eq!(to_vec(&list), vec![4, 3, 2, 1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn swap_out_of_bounds() {
+        list_from(&[1, 2]).swap(0, 2);
+    }
+
+    #[test]
+    fn rotate() {
+        let mut list = list_from(&[1, 2, 3, 4, 5]);
+        list.rotate_left(2);
+        assert_eq!(to_vec(&list), vec![3, 4, 5, 1, 2]);
+        list.rotate_right(2);
+        assert_eq!(to_vec(&list), vec![1, 2, 3, 4, 5]);
+        list.rotate_right(1);
+        assert_eq!(to_vec(&list), vec![5, 1, 2, 3, 4]);
+        list.rotate_left(0);
+        list.rotate_left(5);
+        assert_eq!(to_vec(&list), vec![5, 1, 2, 3, 4]);
+        assert_eq!(list.len(), 5);
+
+        let mut empty: List<i32> = List::new();
+        empty.rotate_left(0);
+        empty.rotate_right(0);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn rotate_out_of_bounds() {
+        list_from(&[1, 2]).rotate_left(3);
+    }
+
+    #[test]
+    fn dedup() {
+        let mut list = list_from(&[1, 1, 2, 3, 3, 3, 1, 4, 4]);
+        list.dedup();
+        assert_eq!(to_vec(&list), vec![1, 2, 3, 1, 4]);
+        assert_eq!(list.len(), 5);
+
+        let mut list = list_from(&[10, 11, 20, 21, 22, 30]);
+        list.dedup_by_key(|x| *x / 10);
+        assert_eq!(to_vec(&list), vec![10, 20, 30]);
+
+        // a is the later element, b the one that is kept
+        let mut list = list_from(&[1, 2, 4, 5, 7]);
+        list.dedup_by(|a, b| *a - *b == 1);
+        assert_eq!(to_vec(&list), vec![1, 4, 7]);
+
+        let mut empty: List<i32> = List::new();
+        empty.dedup();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn sort() {
+        let mut list = list_from(&[5, 3, 8, 1, 9, 2, 7]);
+        list.sort();
+        assert_eq!(to_vec(&list), vec![1, 2, 3, 5, 7, 8, 9]);
+        assert_eq!(list.len(), 7);
+
+        let mut list = list_from(&[4, 1, 3, 1, 2, 4]);
+        list.sort();
+        assert_eq!(to_vec(&list), vec![1, 1, 2, 3, 4, 4]);
+
+        let mut list = List::new();
+        for i in 0..1000 { list.push((i * 7919) % 1000); }
+        list.sort();
+        assert!(list.iter_eq(0..1000));
+
+        let mut empty: List<i32> = List::new();
+        empty.sort();
+        assert!(empty.is_empty());
+        let mut single = list_from(&[1]);
+        single.sort();
+        assert_eq!(to_vec(&single), vec![1]);
+    }
+
+    #[test]
+    fn sort_by() {
+        let mut list = list_from(&[3, 1, 2]);
+        list.sort_by(|a, b| b.cmp(a));
+        assert_eq!(to_vec(&list), vec![3, 2, 1]);
+
+        // f64 isn't Ord, and the sort has to be stable for equal keys
+        let mut list = List::new();
+        for pair in [(2.5, 'a'), (1.0, 'b'), (2.5, 'c'), (0.5, 'd'), (1.0, 'e')].into_iter().rev() {
+            list.push(pair);
+        }
+        list.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        assert_eq!(list.iter().map(|pair| pair.1).collect::<String>(), "dbeac");
+
+        list.sort_by_key(|pair| pair.1 == 'a' || pair.1 == 'e');
+        assert_eq!(list.iter().map(|pair| pair.1).collect::<String>(), "dbcea");
+    }
+
+    #[test]
+    fn merge_by() {
+        let mut a = List::new();
+        for pair in [(1, 'a'), (2, 'a'), (2, 'b')].into_iter().rev() { a.push(pair); }
+        let mut b = List::new();
+        for pair in [(2, 'x'), (3, 'x')].into_iter().rev() { b.push(pair); }
+
+        // ties on the key keep self's elements first and both inputs in their own order
+        let merged = a.merge_by(b, |l, r| l.0.cmp(&r.0));
+        assert_eq!(merged.len(), 5);
+        assert!(merged.iter_eq([(1, 'a'), (2, 'a'), (2, 'b'), (2, 'x'), (3, 'x')]));
+    }
+
+    #[test]
+    fn partition() {
+        let (even, odd) = list_from(&[1, 2, 3, 4, 5]).partition(|x| x % 2 == 0);
+        assert_eq!(to_vec(&even), vec![2, 4]);
+        assert_eq!(to_vec(&odd), vec![1, 3, 5]);
+        assert_eq!((even.len(), odd.len()), (2, 3));
+
+        let (all, none) = list_from(&[1, 2]).partition(|_| true);
+        assert_eq!(to_vec(&all), vec![1, 2]);
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn map() {
+        let strings = list_from(&[1, 2, 3]).map(|x| x.to_string());
+        assert!(strings.iter_eq(["1", "2", "3"].map(String::from)));
+        assert_eq!(strings.len(), 3);
+
+        let halves = list_from(&[1, 2, 3, 4]).filter_map(|x| if x % 2 == 0 { Some(x / 2) } else { None });
+        assert_eq!(to_vec(&halves), vec![1, 2]);
+        assert_eq!(halves.len(), 2);
+    }
+
+    #[test]
+    fn filter() {
+        let odd = list_from(&[1, 2, 3, 4, 5]).filter(|x| x % 2 == 1);
+        assert_eq!(to_vec(&odd), vec![1, 3, 5]);
+        assert_eq!(odd.len(), 3);
+        assert!(list_from(&[1, 2]).filter(|_| false).is_empty());
+    }
+
+    #[test]
+    fn zip() {
+        let mut values = List::new();
+        values.push('c'); values.push('b'); values.push('a');
+        let zipped = List::zip(list_from(&[1, 2, 3]), values);
+        assert!(zipped.iter_eq([(1, 'a'), (2, 'b'), (3, 'c')]));
+        assert_eq!(zipped.len(), 3);
+
+        let zipped = List::zip(list_from(&[1, 2, 3]), list_from(&[4]));
+        assert!(zipped.iter_eq([(1, 4)]));
+        assert!(List::zip(List::<i32>::new(), list_from(&[1])).is_empty());
+    }
+
+    #[test]
+    fn unzip() {
+        let zipped = List::zip(list_from(&[1, 2, 3]), list_from(&[4, 5, 6]));
+        let (left, right) = zipped.unzip();
+        assert_eq!(to_vec(&left), vec![1, 2, 3]);
+        assert_eq!(to_vec(&right), vec![4, 5, 6]);
+        assert_eq!((left.len(), right.len()), (3, 3));
+    }
+
+    #[test]
+    fn flatten() {
+        let mut nested = List::new();
+        nested.push(list_from(&[5]));
+        nested.push(List::new());
+        nested.push(list_from(&[3, 4]));
+        nested.push(list_from(&[1, 2]));
+        let flat = nested.flatten();
+        assert_eq!(to_vec(&flat), vec![1, 2, 3, 4, 5]);
+        assert_eq!(flat.len(), 5);
+
+        assert!(List::<List<i32>>::new().flatten().is_empty());
+    }
+
+    #[test]
+    fn split_when() {
+        let mut list = list_from(&[1, 2, 0, 3, 0, 4]);
+        let tail = list.split_when(|x| *x == 0);
+        assert_eq!(to_vec(&list), vec![1, 2]);
+        assert_eq!(to_vec(&tail), vec![0, 3, 0, 4]);
+        assert_eq!((list.len(), tail.len()), (2, 4));
+
+        let none = list.split_when(|x| *x > 10);
+        assert!(none.is_empty());
+        assert_eq!(list.len(), 2);
+
+        let all = list.split_when(|_| true);
+        assert!(list.is_empty());
+        assert_eq!(to_vec(&all), vec![1, 2]);
+    }
+
+    #[test]
+    fn prepend() {
+        let mut list = list_from(&[3, 4]);
+        list.prepend(list_from(&[1, 2]));
+        assert_eq!(to_vec(&list), vec![1, 2, 3, 4]);
+        assert_eq!(list.len(), 4);
+
+        list.prepend(List::new());
+        assert_eq!(to_vec(&list), vec![1, 2, 3, 4]);
+
+        let mut empty = List::new();
+        empty.prepend(list_from(&[1]));
+        assert_eq!(to_vec(&empty), vec![1]);
+    }
+
+    #[test]
+    fn pop_if() {
+        let mut list = list_from(&[1, 2]);
+        assert_eq!(list.pop_if(|x| *x == 2), None);
+        assert_eq!(list.pop_if(|x| *x == 1), Some(1));
+        assert_eq!(list.pop_if(|x| {
+            *x = 20;
+            false
+        }), None);
+        assert_eq!(to_vec(&list), vec![20]);
+        assert_eq!(list.pop_if(|_| true), Some(20));
+        assert_eq!(list.pop_if(|_| true), None);
+    }
+
+    #[test]
+    fn remove_where() {
+        let mut list = list_from(&[1, 2, 3, 4, 5, 6]);
+        assert_eq!(list.remove_first_where(|x| x % 2 == 0), Some(2));
+        assert_eq!(list.remove_first_where(|x| *x > 10), None);
+        assert_eq!(to_vec(&list), vec![1, 3, 4, 5, 6]);
+
+        assert_eq!(list.remove_all_where(|x| x % 2 == 1), 3);
+        assert_eq!(to_vec(&list), vec![4, 6]);
+        assert_eq!(list.remove_all_where(|x| *x > 10), 0);
+        assert_eq!(list.len(), 2);
+    }
+
+    #[test]
+    fn from_iter_and_extend() {
+        let mut list: List<i32> = (1..=3).collect();
+        assert_eq!(to_vec(&list), vec![1, 2, 3]);
+        assert_eq!(list.len(), 3);
+
+        list.extend(vec![4, 5]);
+        assert_eq!(to_vec(&list), vec![1, 2, 3, 4, 5]);
+        assert_eq!(list.len(), 5);
+
+        let evens: List<i32> = list.into_iter().filter(|x| x % 2 == 0).collect();
+        assert_eq!(to_vec(&evens), vec![2, 4]);
+
+        let empty: List<i32> = std::iter::empty().collect();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn into_iterator() {
+        let mut list = list_from(&[1, 2, 3]);
+        for value in &mut list {
+            *value *= 2;
+        }
+        let mut seen = Vec::new();
+        for value in &list {
+            seen.push(*value);
+        }
+        assert_eq!(seen, vec![2, 4, 6]);
+
+        let mut owned = Vec::new();
+        for value in list {
+            owned.push(value);
+        }
+        assert_eq!(owned, vec![2, 4, 6]);
+    }
+
+    #[test]
+    fn clone() {
+        let list = list_from(&[1, 2, 3]);
+        let copy = list.clone();
+        assert_eq!(to_vec(&copy), vec![1, 2, 3]);
+        assert_eq!(copy.len(), 3);
+
+        let mut long = list_from(&[9, 9, 9, 9, 9]);
+        long.clone_from(&list);
+        assert_eq!(to_vec(&long), vec![1, 2, 3]);
+        assert_eq!(long.len(), 3);
+
+        let mut short = list_from(&[9]);
+        short.clone_from(&list);
+        assert_eq!(to_vec(&short), vec![1, 2, 3]);
+        assert_eq!(short.len(), 3);
+
+        let mut big = List::new();
+        for i in 0..200_000 { big.push(i); }
+        assert_eq!(big.clone().len(), 200_000);
+    }
+
+    #[test]
+    fn debug() {
+        let mut list = list_from(&[3, 2, 1]);
+        assert_eq!(format!("{:?}", list), "[3, 2, 1]");
+        assert_eq!(format!("{:?}", List::<i32>::new()), "[]");
+
+        let mut iter = list.iter();
+        iter.next();
+        assert_eq!(format!("{:?}", iter), "[2, 1]");
+
+        let (front, back) = list.split_iter_mut(1);
+        assert_eq!(format!("{:?}", front), "[3]");
+        assert_eq!(format!("{:?}", back), "[2, 1]");
+        let mut iter = list.iter_mut();
+        iter.next();
+        assert_eq!(format!("{:?}", iter), "[2, 1]");
+
+        let mut iter = list.into_iter();
+        iter.next();
+        assert_eq!(format!("{:?}", iter), "[2, 1]");
+
+        #[derive(Debug)]
+        #[allow(dead_code)]
+        struct Wrapper(List<i32>);
+        assert_eq!(format!("{:?}", Wrapper(list_from(&[1]))), "Wrapper([1])");
+    }
+
+    #[test]
+    fn display() {
+        let list = list_from(&[3, 2, 1]);
+        assert_eq!(list.to_string(), "3 -> 2 -> 1");
+        assert_eq!(list.display_with(", ").to_string(), "3, 2, 1");
+        assert_eq!(list_from(&[1]).to_string(), "1");
+        assert_eq!(List::<i32>::new().to_string(), "");
+    }
+
+    #[test]
+    fn eq() {
+        let list = list_from(&[1, 2, 3]);
+        assert_eq!(list, list_from(&[1, 2, 3]));
+        assert_ne!(list, list_from(&[1, 2]));
+        assert_ne!(list, list_from(&[1, 2, 4]));
+        assert_eq!(List::<i32>::new(), List::new());
+
+        assert_eq!(list, vec![1, 2, 3]);
+        assert_ne!(list, vec![3, 2, 1]);
+        assert_eq!(list, &[1, 2, 3][..]);
+        assert_ne!(list, &[1, 2, 3, 4][..]);
+    }
+
+    #[test]
+    fn ord() {
+        assert!(list_from(&[1, 2]) < list_from(&[1, 3]));
+        assert!(list_from(&[1, 2]) < list_from(&[1, 2, 0]));
+        assert!(List::new() < list_from(&[0]));
+        assert_eq!(list_from(&[2]).cmp(&list_from(&[1, 9])), Ordering::Greater);
+        assert_eq!(list_from(&[1, 2]).cmp(&list_from(&[1, 2])), Ordering::Equal);
+
+        let mut nested: List<List<i32>> = [list_from(&[2]), list_from(&[1, 5]), list_from(&[1])].into_iter().collect();
+        nested.sort();
+        assert!(nested.iter_eq([list_from(&[1]), list_from(&[1, 5]), list_from(&[2])]));
+
+        let mut map = std::collections::BTreeMap::new();
+        map.insert(list_from(&[1, 2]), "a");
+        assert_eq!(map.get(&list_from(&[1, 2])), Some(&"a"));
+    }
+
+    #[test]
+    fn hash() {
+        use std::collections::HashSet;
+
+        let mut set = HashSet::new();
+        assert!(set.insert(list_from(&[1, 2])));
+        assert!(!set.insert(list_from(&[1, 2])));
+        assert!(set.insert(list_from(&[2, 1])));
+        assert!(set.insert(List::new()));
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&list_from(&[2, 1])));
+    }
+
+    #[test]
+    fn default() {
+        static EMPTY: List<i32> = List::new();
+        assert!(EMPTY.is_empty());
+
+        #[derive(Default)]
+        struct Holder {
+            list: List<i32>,
+        }
+        let mut holder = Holder::default();
+        assert!(holder.list.is_empty());
+        holder.list.push(1);
+        assert_eq!(holder.list.peek(), Some(&1));
+    }
+
+    #[test]
+    fn index() {
+        let mut list = list_from(&[1, 2, 3]);
+        assert_eq!(list[0], 1);
+        assert_eq!(list[2], 3);
+        list[2] = 30;
+        list[0] += 10;
+        assert_eq!(list, vec![11, 2, 30]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds() {
+        let list = list_from(&[1, 2, 3]);
+        let _ = list[3];
+    }
+
+    #[test]
+    fn double_ended() {
+        let mut list = list_from(&[1, 2, 3, 4]);
+
+        assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+        let mut iter = list.iter();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!(iter.next_back(), Some(&3));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        let mut iter = list.iter_mut();
+        assert_eq!(iter.next(), Some(&mut 1));
+        *iter.next_back().unwrap() = 40;
+        *iter.next().unwrap() = 20;
+        assert_eq!(iter.next_back(), Some(&mut 3));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(list, vec![1, 20, 3, 40]);
+
+        for value in list.iter_mut().rev().take(2) {
+            *value += 1;
+        }
+        assert_eq!(list, vec![1, 20, 4, 41]);
+
+        let mut iter = list.into_iter();
+        assert_eq!(iter.next_back(), Some(41));
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.rev().collect::<Vec<_>>(), vec![4, 20]);
+    }
+
+    #[test]
+    fn double_ended_buffers_once() {
+        let list: List<i32> = (0..100_000).collect();
+        assert!(list.iter().rev().copied().eq((0..100_000).rev()));
+
+        let mut iter = list.iter();
+        assert_eq!(iter.next_back(), Some(&99_999));
+        assert_eq!(iter.len(), 99_999);
+        assert_eq!(iter.peek_nth(1), Some(&1));
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.peek(), Some(&1));
+        assert_eq!(iter.nth(99_996), Some(&99_997));
+        assert_eq!(format!("{:?}", iter), "[99998]");
+        assert!(list.into_iter().rev().eq((0..100_000).rev()));
+
+        // a piece of split must only buffer its own nodes, not the rest of the chain behind it
+        let list = list_from(&[1, 2, 0, 3, 4, 0, 5]);
+        let mut pieces = list.split(|&x| x == 0);
+        let mut first = pieces.next().unwrap();
+        assert_eq!(first.next_back(), Some(&2));
+        assert_eq!(first.next_back(), Some(&1));
+        assert_eq!(first.next_back(), None);
+        assert_eq!(first.next(), None);
+        assert_eq!(pieces.next().unwrap().rev().collect::<Vec<_>>(), vec![&4, &3]);
+
+        let mut iter = list_from(&[1, 2, 3, 4, 5]).into_iter();
+        assert_eq!(iter.next_back(), Some(5));
+        assert_eq!(iter.len(), 4);
+        assert_eq!(format!("{:?}", iter), "[1, 2, 3, 4]");
+        assert_eq!(iter.advance_by(1), Ok(()));
+        assert_eq!(iter.rev_in_place().collect::<Vec<_>>(), vec![4, 3, 2]);
+    }
+
+    #[test]
+    fn exact_size() {
+        let mut list = list_from(&[1, 2, 3]);
+
+        let mut iter = list.iter();
+        assert_eq!(iter.len(), 3);
+        iter.next();
+        iter.next_back();
+        assert_eq!(iter.size_hint(), (1, Some(1)));
+        iter.next();
+        assert_eq!(iter.len(), 0);
+
+        let mut iter = list.iter_mut();
+        assert_eq!(iter.len(), 3);
+        iter.next_back();
+        assert_eq!(iter.len(), 2);
+        iter.next();
+        assert_eq!(iter.len(), 1);
+
+        let (front, back) = list.split_iter_mut(1);
+        assert_eq!((front.len(), back.len()), (1, 2));
+
+        let mut iter = list.into_iter();
+        assert_eq!(iter.len(), 3);
+        iter.next();
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+    }
+
+    #[test]
+    fn fused() {
+        fn assert_fused<I: std::iter::FusedIterator>(mut iter: I) {
+            while iter.next().is_some() {}
+            for _ in 0..3 {
+                assert!(iter.next().is_none());
+            }
+        }
+
+        let mut list = list_from(&[1, 2]);
+        assert_fused(list.iter());
+        assert_fused(list.iter_mut());
+        assert_fused(list.into_iter());
+    }
+
+    #[test]
+    fn rev_in_place() {
+        let mut iter = list_from(&[1, 2, 3, 4]).into_iter();
+        assert_eq!(iter.next(), Some(1));
+        let rest: Vec<i32> = iter.rev_in_place().collect();
+        assert_eq!(rest, vec![4, 3, 2]);
+
+        assert_eq!(List::<i32>::new().into_iter().rev_in_place().next(), None);
+    }
+
+    #[test]
+    fn iter_pairs() {
+        let list = list_from(&[1, 4, 9, 16]);
+        let deltas: Vec<i32> = list.iter_pairs().map(|(a, b)| b - a).collect();
+        assert_eq!(deltas, vec![3, 5, 7]);
+
+        assert_eq!(list_from(&[1]).iter_pairs().next(), None);
+        assert_eq!(List::<i32>::new().iter_pairs().next(), None);
+    }
+
+    #[test]
+    fn into_chunks() {
+        let chunks: Vec<List<i32>> = list_from(&[1, 2, 3, 4, 5]).into_chunks(2).collect();
+        assert_eq!(chunks, vec![list_from(&[1, 2]), list_from(&[3, 4]), list_from(&[5])]);
+        assert_eq!(chunks[2].len(), 1);
+
+        let chunks: Vec<List<i32>> = list_from(&[1, 2]).into_chunks(5).collect();
+        assert_eq!(chunks, vec![list_from(&[1, 2])]);
+        assert_eq!(List::<i32>::new().into_chunks(1).count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn into_chunks_zero() {
+        list_from(&[1]).into_chunks(0);
+    }
+
+    #[test]
+    fn split() {
+        let list = list_from(&[1, 2, 0, 3, 0, 0, 4]);
+        let runs: Vec<Vec<i32>> = list.split(|x| *x == 0).map(|run| run.copied().collect()).collect();
+        assert_eq!(runs, vec![vec![1, 2], vec![3], vec![], vec![4]]);
+
+        let runs: Vec<Vec<i32>> = list_from(&[0, 1, 0]).split(|x| *x == 0).map(|run| run.copied().collect()).collect();
+        assert_eq!(runs, vec![vec![], vec![1], vec![]]);
+        assert_eq!(List::<i32>::new().split(|x| *x == 0).count(), 1);
+
+        let runs: Vec<List<i32>> = list.into_split(|x| *x == 0).collect();
+        assert_eq!(runs, vec![list_from(&[1, 2]), list_from(&[3]), List::new(), list_from(&[4])]);
+        assert_eq!(runs[0].len(), 2);
+
+        let runs: Vec<List<i32>> = list_from(&[0, 1, 0]).into_split(|x| *x == 0).collect();
+        assert_eq!(runs, vec![List::new(), list_from(&[1]), List::new()]);
+        assert_eq!(List::<i32>::new().into_split(|x| *x == 0).count(), 1);
+    }
+
+    #[test]
+    fn node_handles() {
+        let mut list = list_from(&[1, 2, 3, 4, 5]);
+        // mark during one pass, edit afterwards
+        let marked: Vec<_> = list.iter_handles().filter(|(_, x)| *x % 2 == 0).map(|(handle, _)| handle).collect();
+        let first = list.iter_handles().next().unwrap().0;
+
+        assert_eq!(list.insert_after_handle(marked[0], 20), Ok(list.iter_handles().nth(2).unwrap().0));
+        assert_eq!(list, vec![1, 2, 20, 3, 4, 5]);
+        assert_eq!(list.remove_by_handle(marked[1]), Some(4));
+        assert_eq!(list.remove_by_handle(first), Some(1));
+        assert_eq!(list, vec![2, 20, 3, 5]);
+        assert_eq!(list.len(), 4);
+
+        let mut other = list_from(&[7]);
+        let foreign = other.iter_handles().next().unwrap().0;
+        assert_eq!(list.remove_by_handle(foreign), None);
+        assert_eq!(list.insert_after_handle(foreign, 8), Err(8));
+        assert_eq!(list.len(), 4);
+        assert_eq!(other.remove_by_handle(foreign), Some(7));
+
+        // the freed node's allocation is likely reused by the next push, but the stale handle must not match it
+        let stale = list.iter_handles().next().unwrap().0;
+        assert_eq!(list.pop(), Some(2));
+        list.push(9);
+        assert_eq!(list.remove_by_handle(stale), None);
+        assert_eq!(list.insert_after_handle(stale, 8), Err(8));
+        assert_eq!(list, vec![9, 20, 3, 5]);
+
+        // handles survive relinking, and moving nodes into another list
+        let three = list.iter_handles().nth(2).unwrap().0;
+        list.reverse();
+        let mut moved = list.split_off(0);
+        assert_eq!(list.remove_by_handle(three), None);
+        assert_eq!(moved.remove_by_handle(three), Some(3));
+    }
+
+    #[test]
+    fn peek_nth() {
+        let list = list_from(&[1, 2, 3, 4]);
+        let mut iter = list.iter();
+        assert_eq!(iter.peek(), Some(&1));
+        assert_eq!(iter.peek_nth(2), Some(&3));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.peek_nth(2), Some(&4));
+        assert_eq!(iter.peek_nth(3), None);
+
+        // looking ahead respects elements already taken from the back
+        iter.next_back();
+        assert_eq!(iter.peek_nth(1), Some(&3));
+        assert_eq!(iter.peek_nth(2), None);
+        assert_eq!(iter.next(), Some(&2));
+    }
+
+    #[test]
+    fn cursor() {
+        let list = list_from(&[1, 2, 3]);
+        let mut cursor = list.cursor_front();
+        assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&1)));
+        assert_eq!(cursor.peek_next(), Some(&2));
+
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&3)));
+        assert_eq!(cursor.peek_next(), None);
+
+        cursor.move_next();
+        assert_eq!((cursor.index(), cursor.current()), (None, None));
+
+        let empty: List<i32> = List::new();
+        assert_eq!(empty.cursor_front().current(), None);
+    }
+
+    #[test]
+    fn cursor_ghost() {
+        let list = list_from(&[1, 2, 3]);
+        let mut cursor = list.cursor_back();
+        assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&3)));
+        assert_eq!(cursor.peek_prev(), Some(&2));
+
+        // tail -> ghost -> head, and back again
+        cursor.move_next();
+        assert_eq!((cursor.index(), cursor.current()), (None, None));
+        assert_eq!((cursor.peek_next(), cursor.peek_prev()), (Some(&1), Some(&3)));
+        cursor.move_next();
+        assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&1)));
+        assert_eq!(cursor.peek_prev(), None);
+        cursor.move_prev();
+        assert_eq!((cursor.index(), cursor.current()), (None, None));
+        cursor.move_prev();
+        assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&3)));
+        cursor.move_prev();
+        assert_eq!((cursor.index(), cursor.current()), (Some(1), Some(&2)));
+
+        let empty: List<i32> = List::new();
+        let mut cursor = empty.cursor_back();
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        cursor.move_prev();
+        assert_eq!((cursor.index(), cursor.current()), (None, None));
+
+        let mut list = list_from(&[1, 2, 3]);
+        {
+            let mut cursor = list.cursor_back_mut();
+            assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&mut 3)));
+            assert_eq!(cursor.peek_prev(), Some(&mut 2));
+            cursor.move_next();
+            assert_eq!((cursor.index(), cursor.current()), (None, None));
+            assert_eq!(cursor.peek_next(), Some(&mut 1));
+            assert_eq!(cursor.peek_prev(), Some(&mut 3));
+            cursor.move_next();
+            assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&mut 1)));
+            cursor.move_prev();
+            assert_eq!((cursor.index(), cursor.current()), (None, None));
+            cursor.move_prev();
+            *cursor.current().unwrap() = 30;
+            cursor.move_prev();
+            assert_eq!((cursor.index(), cursor.current()), (Some(1), Some(&mut 2)));
+        }
+        assert_eq!(list, vec![1, 2, 30]);
+
+        let mut empty: List<i32> = List::new();
+        let mut cursor = empty.cursor_back_mut();
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        cursor.move_prev();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.peek_next(), None);
+        assert_eq!(cursor.peek_prev(), None);
+    }
+
+    #[test]
+    fn cursor_mut() {
+        let mut list = list_from(&[1, 2, 3]);
+        {
+            let mut cursor = list.cursor_front_mut();
+            assert_eq!(cursor.current(), Some(&mut 1));
+            cursor.insert_before(0);
+            assert_eq!((cursor.index(), cursor.current()), (Some(1), Some(&mut 1)));
+            cursor.insert_after(10);
+            assert_eq!(cursor.peek_next(), Some(&mut 10));
+
+            cursor.move_next();
+            *cursor.current_mut().unwrap() = 15;
+            cursor.move_next();
+            assert_eq!(cursor.remove_current(), Some(2));
+            assert_eq!((cursor.index(), cursor.current()), (Some(3), Some(&mut 3)));
+
+            cursor.move_next();
+            assert_eq!(cursor.current(), None);
+            assert_eq!(cursor.remove_current(), None);
+            // past the tail: before means at the back, after means at the front
+            cursor.insert_before(4);
+            cursor.insert_after(-1);
+            assert_eq!(cursor.index(), None);
+        }
+        assert_eq!(list, vec![-1, 0, 1, 15, 3, 4]);
+        assert_eq!(list.len(), 6);
+
+        let mut empty = List::new();
+        {
+            let mut cursor = empty.cursor_front_mut();
+            cursor.insert_after(2);
+            cursor.insert_after(1);
+        }
+        assert_eq!(empty, vec![1, 2]);
+        assert_eq!(empty.len(), 2);
+    }
+
+    #[test]
+    fn cursor_splice() {
+        let mut list = list_from(&[1, 5]);
+        {
+            let mut cursor = list.cursor_front_mut();
+            cursor.splice_after(list_from(&[2, 3]));
+            assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&mut 1)));
+            cursor.move_next();
+            cursor.move_next();
+            cursor.move_next();
+            cursor.splice_before(list_from(&[4]));
+            assert_eq!((cursor.index(), cursor.current()), (Some(4), Some(&mut 5)));
+            cursor.splice_after(List::new());
+            cursor.splice_before(List::new());
+
+            cursor.move_next();
+            assert_eq!(cursor.current(), None);
+            cursor.splice_before(list_from(&[6, 7]));
+            cursor.splice_after(list_from(&[-1, 0]));
+            assert_eq!(cursor.index(), None);
+        }
+        assert_eq!(list, vec![-1, 0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(list.len(), 9);
+    }
+
+    #[test]
+    fn cursor_split() {
+        let mut list = list_from(&[1, 2, 3, 4, 5]);
+        let (before, after) = {
+            let mut cursor = list.cursor_front_mut();
+            cursor.move_next();
+            cursor.move_next();
+            let after = cursor.split_after();
+            let before = cursor.split_before();
+            assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&mut 3)));
+            (before, after)
+        };
+        assert_eq!(before, vec![1, 2]);
+        assert_eq!(after, vec![4, 5]);
+        assert_eq!(list, vec![3]);
+        assert_eq!((before.len(), after.len(), list.len()), (2, 2, 1));
+
+        // past the tail both take everything
+        let mut list = list_from(&[1, 2]);
+        let all = {
+            let mut cursor = list.cursor_front_mut();
+            cursor.move_next();
+            cursor.move_next();
+            cursor.split_after()
+        };
+        assert_eq!(all, vec![1, 2]);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn cursor_seek() {
+        let list = list_from(&[1, 2, 3, 4]);
+        let mut cursor = list.cursor_front();
+        cursor.seek_forward(2);
+        assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&3)));
+        cursor.seek_to(1);
+        assert_eq!((cursor.index(), cursor.current()), (Some(1), Some(&2)));
+        cursor.seek_to(3);
+        assert_eq!((cursor.index(), cursor.current()), (Some(3), Some(&4)));
+        cursor.seek_forward(10);
+        assert_eq!((cursor.index(), cursor.current()), (None, None));
+        cursor.seek_to(0);
+        assert_eq!(cursor.current(), Some(&1));
+
+        let mut list = list_from(&[1, 2, 3, 4]);
+        {
+            let mut cursor = list.cursor_front_mut();
+            cursor.seek_to(3);
+            assert_eq!((cursor.index(), cursor.current()), (Some(3), Some(&mut 4)));
+            cursor.seek_to(1);
+            assert_eq!((cursor.index(), cursor.current()), (Some(1), Some(&mut 2)));
+            cursor.insert_before(10);
+            cursor.seek_forward(2);
+            assert_eq!((cursor.index(), cursor.current()), (Some(4), Some(&mut 4)));
+            cursor.seek_to(9);
+            assert_eq!((cursor.index(), cursor.current()), (None, None));
+        }
+        assert_eq!(list, vec![1, 10, 2, 3, 4]);
+    }
+
+    #[test]
+    fn entry() {
+        use super::Entry;
+        let mut list = list_from(&[1, 2]);
+
+        // occupied: the default is never built
+        *list.entry(0).or_insert_with(|| unreachable!()) += 10;
+        assert_eq!(list.entry(1).and_modify(|e| *e *= 2).or_insert(0), &mut 4);
+        // vacant: the slot past the end gets filled
+        assert_eq!(list.entry(2).or_insert_with(|| 3), &mut 3);
+        assert_eq!(to_vec(&list), vec![11, 4, 3]);
+        assert_eq!(list.len(), 3);
+
+        match list.entry(1) {
+            Entry::Occupied(entry) => assert_eq!(entry.remove(), 4),
+            Entry::Vacant(_) => panic!("index 1 should be occupied"),
+        }
+        assert!(matches!(list.entry(2), Entry::Vacant(_)));
+        assert_eq!(to_vec(&list), vec![11, 3]);
+        assert_eq!(list.len(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn entry_past_the_end() {
+        let mut list = list_from(&[1]);
+        list.entry(2);
+    }
+
+    #[cfg(feature = "sort-steps")]
+    #[test]
+    fn sort_steps() {
+        let mut list = list_from(&[3, 1, 4, 1, 5, 9, 2, 6, 5]);
+        let handles: Vec<_> = list.iter_handles().map(|(handle, _)| handle).collect();
+        let mut steps = list.sort_steps();
+
+        // width 1: (3 1) swap, (4 1) swap, (5 9) and (2 6) are already in order, 5 is alone
+        let step = steps.next().unwrap();
+        assert_eq!(step.width(), 1);
+        assert_eq!(step.relinked(), &[handles[1], handles[3]]);
+        assert_eq!(to_vec(steps.list()), vec![1, 3, 1, 4, 5, 9, 2, 6, 5]);
+
+        // width 2: the second 1 overtakes 3, then 2 overtakes 5 and 9, then 6 overtakes 9
+        let step = steps.next().unwrap();
+        assert_eq!(step.width(), 2);
+        assert_eq!(step.relinked(), &[handles[3], handles[6], handles[7]]);
+        assert_eq!(to_vec(steps.list()), vec![1, 1, 3, 4, 2, 5, 6, 9, 5]);
+
+        let widths: Vec<_> = steps.by_ref().map(|step| step.width()).collect();
+        assert_eq!(widths, vec![4, 8]);
+        assert_eq!(to_vec(steps.list()), vec![1, 1, 2, 3, 4, 5, 5, 6, 9]);
+        assert_eq!(steps.next(), None);
+
+        // the nodes were relinked rather than copied, and the len is unchanged
+        assert_eq!(list.len(), 9);
+        let mut sorted: Vec<_> = handles.clone();
+        sorted.sort_by_key(|handle| list.iter_handles().position(|(h, _)| h == *handle));
+        assert_eq!(sorted, vec![handles[1], handles[3], handles[6], handles[0], handles[2], handles[4], handles[8], handles[7], handles[5]]);
+    }
+
+    #[test]
+    fn two_cursors() {
+        let list = list_from(&[1, 2, 3, 4, 5]);
+
+        // midpoint: fast moves two for every one of slow
+        let mut slow = list.cursor_front();
+        let mut fast = slow;
+        while fast.peek_next().is_some() {
+            fast.seek_forward(2);
+            slow.move_next();
+        }
+        assert_eq!(slow.current(), Some(&3));
+        assert_eq!(fast.current(), Some(&5));
+        assert!(slow < fast);
+
+        let mut other = list.cursor_front();
+        assert!(other < slow);
+        other.seek_to(2);
+        assert!(other == slow);
+        assert_eq!(other.partial_cmp(&slow), Some(Ordering::Equal));
+
+        // same contents, different list
+        let copy = list.clone();
+        let elsewhere = copy.cursor_front();
+        assert!(elsewhere != list.cursor_front());
+        assert_eq!(elsewhere.partial_cmp(&list.cursor_front()), None);
+    }
+
+    #[test]
+    fn from_first() {
+        let mut first = crate::first::List::new();
+        first.push(String::from("c"));
+        first.push(String::from("b"));
+        first.push(String::from("a"));
+
+        let second = List::from(first);
+        assert_eq!(second.iter().map(String::as_str).collect::<Vec<_>>(), vec!["a", "b", "c"]);
+        assert_eq!(second.len(), 3);
+
+        let first = crate::first::List::from(second);
+        assert_eq!(first.len(), 3);
+        let back: Vec<_> = first.into_iter().collect();
+        assert_eq!(back, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn forgotten_cursor_mut() {
+        let mut list = list_from(&[1, 2, 3]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+        // the walked-past nodes leak, but len still matches what is linked
+        std::mem::forget(cursor);
+        assert_eq!(to_vec(&list), vec![3]);
+        assert_eq!(list.len(), 1);
+        assert_eq!(list.iter().len(), 1);
+        list.insert(1, 9);
+        assert_eq!(to_vec(&list), vec![3, 9]);
+
+        // and a cursor that is dropped normally puts everything back
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.insert_before(8);
+        cursor.splice_before(list_from(&[7]));
+        drop(cursor);
+        assert_eq!(to_vec(&list), vec![3, 8, 7, 9]);
+        assert_eq!(list.len(), 4);
+    }
+}
@@ -0,0 +1,984 @@
+/*
+    A persistent list: once built, a list never changes. "Adding" an element makes a new list whose first node
+    points at the old list, so the two share everything after the new head:
+
+    list1 = A -> B -> C -> *null*
+    list2 = tail(list1) = B -> C -> *null*
+    list3 = prepend(list2, X) = X -> B -> C -> *null*
+
+    B and C are owned by all three lists at once. A Box can only have one owner, so the links are Rc instead:
+    a reference-counted pointer that frees the node when the last list pointing at it goes away.
+    Rc only hands out shared references, which is fine since a shared node is never mutated (get_mut copies it first).
+
+    Rc counts with plain integers, so it can't cross threads. Arc is the same pointer with atomic counts.
+    Instead of writing the list twice, it is generic over a PointerKind that says which of the two to use:
+    List<T> is the Rc version and ArcList<T> is the Arc one, which is Send and Sync when T is.
+*/
+use std::collections::HashSet;
+use std::iter::FusedIterator;
+use std::ops::Deref;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/*
+    A family of shared pointers: Ptr<U> is the pointer type for any pointee U (a generic associated type).
+    Only the operations the list needs are here; everything else goes through Deref and Clone.
+*/
+pub trait PointerKind {
+    type Ptr<U>: Deref<Target = U> + Clone;
+
+    fn new<U>(value: U) -> Self::Ptr<U>;
+
+    // gives the value back if this was the only pointer to it
+    fn try_unwrap<U>(ptr: Self::Ptr<U>) -> Result<U, Self::Ptr<U>>;
+
+    /*
+        Drops this pointer and gives the value back if it was the last one. Unlike try_unwrap this can't race:
+        if two threads let go of the last two Arcs at once, exactly one of them gets the value.
+    */
+    fn into_inner<U>(ptr: Self::Ptr<U>) -> Option<U>;
+
+    // true if both point at the same allocation
+    fn ptr_eq<U>(a: &Self::Ptr<U>, b: &Self::Ptr<U>) -> bool;
+
+    // mutable access, cloning the value into a fresh allocation first if anyone else points at it
+    fn make_mut<U: Clone>(ptr: &mut Self::Ptr<U>) -> &mut U;
+
+    // how many pointers to this allocation exist, the pointer passed in included
+    fn strong_count<U>(ptr: &Self::Ptr<U>) -> usize;
+}
+
+// Marker types that only name a pointer kind, there is nothing to construct.
+pub enum RcKind {}
+pub enum ArcKind {}
+
+impl PointerKind for RcKind {
+    type Ptr<U> = Rc<U>;
+
+    fn new<U>(value: U) -> Rc<U> {
+        Rc::new(value)
+    }
+
+    fn try_unwrap<U>(ptr: Rc<U>) -> Result<U, Rc<U>> {
+        Rc::try_unwrap(ptr)
+    }
+
+    fn into_inner<U>(ptr: Rc<U>) -> Option<U> {
+        Rc::into_inner(ptr)
+    }
+
+    fn ptr_eq<U>(a: &Rc<U>, b: &Rc<U>) -> bool {
+        Rc::ptr_eq(a, b)
+    }
+
+    fn make_mut<U: Clone>(ptr: &mut Rc<U>) -> &mut U {
+        Rc::make_mut(ptr)
+    }
+
+    fn strong_count<U>(ptr: &Rc<U>) -> usize {
+        Rc::strong_count(ptr)
+    }
+}
+
+impl PointerKind for ArcKind {
+    type Ptr<U> = Arc<U>;
+
+    fn new<U>(value: U) -> Arc<U> {
+        Arc::new(value)
+    }
+
+    fn try_unwrap<U>(ptr: Arc<U>) -> Result<U, Arc<U>> {
+        Arc::try_unwrap(ptr)
+    }
+
+    fn into_inner<U>(ptr: Arc<U>) -> Option<U> {
+        Arc::into_inner(ptr)
+    }
+
+    fn ptr_eq<U>(a: &Arc<U>, b: &Arc<U>) -> bool {
+        Arc::ptr_eq(a, b)
+    }
+
+    fn make_mut<U: Clone>(ptr: &mut Arc<U>) -> &mut U {
+        Arc::make_mut(ptr)
+    }
+
+    fn strong_count<U>(ptr: &Arc<U>) -> usize {
+        Arc::strong_count(ptr)
+    }
+}
+
+pub struct SharedList<T, P: PointerKind = RcKind> {
+    root: Link<T, P>,
+}
+
+/*
+    The aliases are what users name. With the generic struct, List::new() alone couldn't tell which pointer to use,
+    but through an alias the kind is fixed and only T is left to infer.
+*/
+pub type List<T> = SharedList<T, RcKind>;
+pub type ArcList<T> = SharedList<T, ArcKind>;
+
+type Link<T, P> = Option<<P as PointerKind>::Ptr<Node<T, P>>>;
+
+struct Node<T, P: PointerKind> {
+    elem: T,
+    /*
+        The length of the list starting at this node. A node's next never changes once it's made, so neither does this,
+        and every version sharing the node agrees on it. That is what makes len() O(1) without a field on the list.
+    */
+    len: usize,
+    next: Link<T, P>,
+}
+
+impl <T, P: PointerKind> SharedList<T, P> {
+    pub const fn new() -> Self {
+        SharedList { root: None }
+    }
+
+    // The old list stays valid, and the new one shares all of it. push() is the in-place version.
+    pub fn prepend(&self, elem: T) -> Self {
+        // clone on an Rc only bumps the count, the nodes aren't copied
+        let len = self.len() + 1;
+        SharedList { root: Some(P::new(Node { elem, len, next: self.root.clone() })) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.root.as_ref().map_or(0, |node| node.len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    // O(index), but out of bounds is known right away from len
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len() {
+            return None;
+        }
+        self.iter().nth(index)
+    }
+
+    // The list without its first element, sharing every node with self. The tail of an empty list is empty.
+    pub fn tail(&self) -> Self {
+        // and_then instead of map, since next is already an Option
+        SharedList { root: self.root.as_ref().and_then(|node| node.next.clone()) }
+    }
+
+    pub fn head(&self) -> Option<&T> {
+        self.root.as_ref().map(|node| &node.elem)
+    }
+}
+
+impl <T, P: PointerKind> Default for SharedList<T, P> {
+    fn default() -> Self {
+        SharedList::new()
+    }
+}
+
+/*
+    The default drop is recursive: dropping a node drops its next link, which drops the next node, and so on,
+    so a long list can overflow the stack. Like second.rs we unlink the nodes in a loop, with one twist:
+    a node can only be taken apart if this list is its last owner. into_inner hands the node back only
+    when ours was the last pointer. As soon as a node is still shared by another list we stop, because that node and
+    everything after it still belongs to the other list.
+
+    This can't use try_unwrap and drop the pointer on Err: with Arc, two threads dropping lists that share a tail
+    could both see a count of 2 and both give up, and the last decrement would then drop the whole tail recursively.
+    into_inner makes checking and decrementing one step (see the std docs of Arc::into_inner).
+*/
+impl <T, P: PointerKind> Drop for SharedList<T, P> {
+    fn drop(&mut self) {
+        let mut link = self.root.take();
+        while let Some(node) = link {
+            match P::into_inner(node) {
+                // node was ours alone: keep its next and let the node itself drop here
+                Some(mut node) => link = node.next.take(),
+                // someone else still holds it, our pointer was already let go
+                None => break,
+            }
+        }
+    }
+}
+
+pub struct Iter<'a, T, P: PointerKind = RcKind> {
+    next: Option<&'a Node<T, P>>,
+}
+
+impl <T, P: PointerKind> SharedList<T, P> {
+    // Only iter: there's no IntoIter or IterMut, since nodes may be shared and we can't move or mutate the elements.
+    pub fn iter(&self) -> Iter<'_, T, P> {
+        Iter { next: self.root.as_deref() }
+    }
+}
+
+impl <'a, T, P: PointerKind> Iterator for Iter<'a, T, P> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            &node.elem
+        })
+    }
+}
+
+// for x in &list
+impl <'a, T, P: PointerKind> IntoIterator for &'a SharedList<T, P> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, P>;
+    fn into_iter(self) -> Iter<'a, T, P> {
+        self.iter()
+    }
+}
+
+// once next is None it stays None
+impl <'a, T, P: PointerKind> FusedIterator for Iter<'a, T, P> {}
+
+// Iter only holds a shared reference, so a copy can walk on independently; derive would require T: Clone.
+impl <'a, T, P: PointerKind> Clone for Iter<'a, T, P> {
+    fn clone(&self) -> Self {
+        Iter { next: self.next }
+    }
+}
+
+impl <T: Clone, P: PointerKind> SharedList<T, P> {
+    /*
+        A new version with the element at index replaced, the old version is unchanged.
+        Nodes can't be changed in place, and the node before the replaced one would have to point somewhere new,
+        so every node up to index is copied (path copying). Everything after index is shared with self.
+        O(index). Panics if index is out of bounds, like indexing a slice.
+    */
+    pub fn update(&self, index: usize, value: T) -> Self {
+        assert!(index < self.len(), "update index (is {}) should be < len (is {})", index, self.len());
+        let mut prefix = Vec::with_capacity(index);
+        let mut node = self.root.as_deref().unwrap();
+        for _ in 0..index {
+            prefix.push(&node.elem);
+            // in bounds, checked above
+            node = node.next.as_deref().unwrap();
+        }
+        let replaced = node;
+
+        // rebuild the copied part back to front on top of the shared tail
+        let mut list = SharedList { root: replaced.next.clone() }.prepend(value);
+        for elem in prefix.into_iter().rev() {
+            list = list.prepend(elem.clone());
+        }
+        list
+    }
+}
+
+impl <T, P: PointerKind> SharedList<T, P> {
+    // puts elems in front of list in their order, so elems[0] ends up as the head
+    fn prepend_vec(elems: Vec<T>, mut list: Self) -> Self {
+        for elem in elems.into_iter().rev() {
+            list = list.prepend(elem);
+        }
+        list
+    }
+
+    // Every element changes, so nothing can be shared: a whole new spine is built.
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> SharedList<U, P> {
+        SharedList::prepend_vec(self.iter().map(f).collect(), SharedList::new())
+    }
+
+    pub fn fold<B, F: FnMut(B, &T) -> B>(&self, init: B, f: F) -> B {
+        self.iter().fold(init, f)
+    }
+
+    /*
+        The elements the predicate accepts, in order. Past the last rejected element the list is unchanged,
+        so that whole suffix is shared with self, and only the kept elements before it are copied.
+        Filtering out nothing copies nothing.
+    */
+    pub fn filter<F: FnMut(&T) -> bool>(&self, mut pred: F) -> Self
+    where
+        T: Clone,
+    {
+        let mut kept = Vec::new();
+        // the kept elements in kept[..copied] come before the last rejected node and need copies
+        let mut copied = 0;
+        let mut suffix = &self.root;
+        let mut link = &self.root;
+        while let Some(node) = link {
+            if pred(&node.elem) {
+                kept.push(&node.elem);
+            } else {
+                copied = kept.len();
+                suffix = &node.next;
+            }
+            link = &node.next;
+        }
+        kept.truncate(copied);
+        let copies = kept.into_iter().cloned().collect();
+        SharedList::prepend_vec(copies, SharedList { root: suffix.clone() })
+    }
+}
+
+// Element by element, after the O(1) length check. For T: Eq, fast_eq can skip the shared part.
+impl <T: PartialEq, P: PointerKind> PartialEq for SharedList<T, P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl <T: Eq, P: PointerKind> SharedList<T, P> {
+    /*
+        Same result as ==, but as soon as both sides reach the very same node the rest is shared and must be equal,
+        so the walk stops there. Versions of one list that differ in a few leading nodes compare in O(those nodes).
+        That shortcut is only right if every element equals itself, which is what Eq promises and PartialEq doesn't
+        (a NaN isn't == itself). This is why Rc takes the same shortcut only for T: Eq, and why it isn't used by ==.
+    */
+    pub fn fast_eq(&self, other: &Self) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+        let (mut a, mut b) = (&self.root, &other.root);
+        while let (Some(x), Some(y)) = (a, b) {
+            if P::ptr_eq(x, y) {
+                return true;
+            }
+            if x.elem != y.elem {
+                return false;
+            }
+            (a, b) = (&x.next, &y.next);
+        }
+        // same length, so both ran out together
+        true
+    }
+}
+
+impl <T: Eq, P: PointerKind> Eq for SharedList<T, P> {}
+
+impl <T: Clone, P: PointerKind> SharedList<T, P> {
+    // A reversed copy. Every node's next changes, so no node can be shared. O(n).
+    pub fn rev(&self) -> Self {
+        self.fold(SharedList::new(), |list, elem| list.prepend(elem.clone()))
+    }
+
+    /*
+        self followed by other. other is shared entirely, but the last node of self has to point at other,
+        and so does (indirectly) every node before it, so self's spine is copied. O(len of self).
+        If other is empty the result is just self, sharing everything.
+    */
+    pub fn append(&self, other: &Self) -> Self {
+        if other.root.is_none() {
+            return SharedList { root: self.root.clone() };
+        }
+        let copies = self.iter().cloned().collect();
+        SharedList::prepend_vec(copies, SharedList { root: other.root.clone() })
+    }
+}
+
+/*
+    A zipper splits the list at a focus into two persistent lists: right starts at the focus,
+    and left holds the elements before it, nearest first. It is the persistent cousin of second.rs's CursorMut,
+    which parks walked-past nodes the same way; here nothing is owned exclusively, so moving copies one element.
+
+    list = 1 -> 2 -> 3 -> 4, focus on 3:  left = 2 -> 1   right = 3 -> 4
+
+    Edits happen at the front of right, so each one is O(1). rebuild() puts left back on top of right:
+    the suffix the zipper never reached is still shared with the original list.
+    The focus can also be one past the end, where right is empty.
+*/
+pub struct Zipper<T, P: PointerKind = RcKind> {
+    left: SharedList<T, P>,
+    right: SharedList<T, P>,
+}
+
+impl <T, P: PointerKind> SharedList<T, P> {
+    // A zipper focused on the head; self isn't affected by anything done through it.
+    pub fn zipper(&self) -> Zipper<T, P> {
+        Zipper { left: SharedList::new(), right: SharedList { root: self.root.clone() } }
+    }
+}
+
+impl <T, P: PointerKind> Zipper<T, P> {
+    pub fn focus(&self) -> Option<&T> {
+        self.right.head()
+    }
+
+    // the index of the focus, O(1) thanks to the memoized length
+    pub fn index(&self) -> usize {
+        self.left.len()
+    }
+}
+
+// moving and editing copy elements between the two halves
+impl <T: Clone, P: PointerKind> Zipper<T, P> {
+    // Moves the focus one step towards the back, false if it is already past the end.
+    pub fn right(&mut self) -> bool {
+        let Some(elem) = self.right.head() else { return false };
+        self.left = self.left.prepend(elem.clone());
+        self.right = self.right.tail();
+        true
+    }
+
+    // Moves the focus one step towards the front, false if it is already on the head.
+    pub fn left(&mut self) -> bool {
+        let Some(elem) = self.left.head() else { return false };
+        self.right = self.right.prepend(elem.clone());
+        self.left = self.left.tail();
+        true
+    }
+
+    // Replaces the focused element. Past the end there is nothing to replace and the value is given back as Err.
+    pub fn set(&mut self, value: T) -> Result<(), T> {
+        if self.right.is_empty() {
+            return Err(value);
+        }
+        self.right = self.right.tail().prepend(value);
+        Ok(())
+    }
+
+    // Inserts before the focus and focuses on the new element. Past the end this appends.
+    pub fn insert(&mut self, value: T) {
+        self.right = self.right.prepend(value);
+    }
+
+    pub fn rebuild(self) -> SharedList<T, P> {
+        let Zipper { left, right } = self;
+        left.fold(right, |list, elem| list.prepend(elem.clone()))
+    }
+}
+
+/*
+    Copy-on-write editing in place, with Vec-like methods on &mut self. Versions are snapshots made with clone(),
+    which only bumps the head's count. An edit copies a node only if some other version still points at it,
+    the way Rc::make_mut does, so a list nobody else shares is edited with no copying at all.
+*/
+impl <T, P: PointerKind> Clone for SharedList<T, P> {
+    fn clone(&self) -> Self {
+        SharedList { root: self.root.clone() }
+    }
+}
+
+// what make_mut copies: one node, whose next is shared with the original
+impl <T: Clone, P: PointerKind> Clone for Node<T, P> {
+    fn clone(&self) -> Self {
+        Node { elem: self.elem.clone(), len: self.len, next: self.next.clone() }
+    }
+}
+
+impl <T, P: PointerKind> SharedList<T, P> {
+    // Never copies: the old head simply moves into the new node.
+    pub fn push(&mut self, elem: T) {
+        let len = self.len() + 1;
+        let next = self.root.take();
+        self.root = Some(P::new(Node { elem, len, next }));
+    }
+}
+
+impl <T: Clone, P: PointerKind> SharedList<T, P> {
+    // The head's element is moved out if this list owns the head alone, and cloned if it is shared.
+    pub fn pop(&mut self) -> Option<T> {
+        let node = self.root.take()?;
+        match P::try_unwrap(node) {
+            Ok(Node { elem, next, .. }) => {
+                self.root = next;
+                Some(elem)
+            },
+            Err(node) => {
+                self.root = node.next.clone();
+                Some(node.elem.clone())
+            },
+        }
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    /*
+        Every node on the way to index has to be made unique, since the nodes before it point (indirectly) at the copy.
+        That is the same path copying as update(), only skipping the nodes this list already owns alone.
+    */
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len() {
+            return None;
+        }
+        let mut link = &mut self.root;
+        for _ in 0..index {
+            link = &mut P::make_mut(link.as_mut()?).next;
+        }
+        link.as_mut().map(|node| &mut P::make_mut(node).elem)
+    }
+}
+
+/*
+    Diagnostics for checking that versions really share memory instead of holding deep copies.
+    Two lists can only share a common tail (once they reach the same node they are the same from there on),
+    so that is what shared_suffix_len measures.
+*/
+impl <T, P: PointerKind> SharedList<T, P> {
+    /*
+        The number of nodes self and other share. Both are first walked down to the same length, which len() makes cheap;
+        from there shared nodes sit at the same distance from the end on both sides, so one lockstep walk finds the first one.
+        O(len of the longer list).
+    */
+    pub fn shared_suffix_len(&self, other: &Self) -> usize {
+        let (mut a, mut b) = (&self.root, &other.root);
+        for _ in other.len()..self.len() {
+            a = &a.as_ref().unwrap().next;
+        }
+        for _ in self.len()..other.len() {
+            b = &b.as_ref().unwrap().next;
+        }
+        while let (Some(x), Some(y)) = (a, b) {
+            if P::ptr_eq(x, y) {
+                return x.len;
+            }
+            (a, b) = (&x.next, &y.next);
+        }
+        0
+    }
+
+    // How many lists and nodes point at the head node, this list included. 0 for an empty list.
+    pub fn strong_count_of_head(&self) -> usize {
+        self.root.as_ref().map_or(0, P::strong_count)
+    }
+
+    /*
+        The number of distinct nodes across all the given lists. With no sharing at all this is the sum of their lengths.
+        Each list is walked until it reaches a node that was already counted, since the rest of it was counted too.
+    */
+    pub fn unique_node_count<'a>(lists: impl IntoIterator<Item = &'a Self>) -> usize
+    where
+        T: 'a,
+        P: 'a,
+    {
+        let mut seen = HashSet::new();
+        for list in lists {
+            let mut link = &list.root;
+            while let Some(node) = link {
+                if !seen.insert(&**node as *const Node<T, P>) {
+                    break;
+                }
+                link = &node.next;
+            }
+        }
+        seen.len()
+    }
+}
+
+/*
+    Undo/redo on top of the persistent list. Every committed version is kept, but since versions share
+    whatever they didn't change, keeping one costs only the nodes its edits copied.
+
+    Edits go to a working copy through current_mut(), using the copy-on-write methods so no committed version is disturbed.
+    commit() records the working copy as a new version and forgets anything that could have been redone.
+    undo() and redo() move between committed versions and reset the working copy to it, dropping uncommitted edits.
+*/
+pub struct History<T, P: PointerKind = RcKind> {
+    versions: Vec<SharedList<T, P>>,
+    // the committed version the working copy started from
+    at: usize,
+    current: SharedList<T, P>,
+}
+
+impl <T, P: PointerKind> History<T, P> {
+    // list becomes the first committed version
+    pub fn new(list: SharedList<T, P>) -> Self {
+        History { versions: vec![list.clone()], at: 0, current: list }
+    }
+
+    pub fn current(&self) -> &SharedList<T, P> {
+        &self.current
+    }
+
+    pub fn current_mut(&mut self) -> &mut SharedList<T, P> {
+        &mut self.current
+    }
+
+    // A cheap handle on the working copy that later edits won't affect. O(1).
+    pub fn snapshot(&self) -> SharedList<T, P> {
+        self.current.clone()
+    }
+
+    pub fn commit(&mut self) {
+        self.versions.truncate(self.at + 1);
+        self.versions.push(self.current.clone());
+        self.at += 1;
+    }
+
+    // Goes back to the version before the current one, false if there is none.
+    pub fn undo(&mut self) -> bool {
+        if self.at == 0 {
+            return false;
+        }
+        self.at -= 1;
+        self.current = self.versions[self.at].clone();
+        true
+    }
+
+    // Goes forward to the version that was last undone, false if there is none.
+    pub fn redo(&mut self) -> bool {
+        if self.at + 1 == self.versions.len() {
+            return false;
+        }
+        self.at += 1;
+        self.current = self.versions[self.at].clone();
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ArcList, List};
+
+    fn list_from(elems: &[i32]) -> List<i32> {
+        elems.iter().rev().fold(List::new(), |list, &elem| list.prepend(elem))
+    }
+
+    #[test]
+    fn basics() {
+        let list = List::new();
+        assert_eq!(list.head(), None);
+
+        let list = list.prepend(1).prepend(2).prepend(3);
+        assert_eq!(list.head(), Some(&3));
+
+        let list = list.tail();
+        assert_eq!(list.head(), Some(&2));
+
+        let list = list.tail();
+        assert_eq!(list.head(), Some(&1));
+
+        let list = list.tail();
+        assert_eq!(list.head(), None);
+
+        // Make sure empty tail works
+        let list = list.tail();
+        assert_eq!(list.head(), None);
+    }
+
+    #[test]
+    fn sharing() {
+        let shared = List::new().prepend(1).prepend(2);
+        let a = shared.prepend(3);
+        let b = shared.prepend(4);
+
+        // both versions see the same tail, and the original is untouched
+        assert_eq!(a.iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+        assert_eq!(b.iter().collect::<Vec<_>>(), vec![&4, &2, &1]);
+        assert_eq!(shared.iter().collect::<Vec<_>>(), vec![&2, &1]);
+        assert!(std::ptr::eq(a.tail().head().unwrap(), b.tail().head().unwrap()));
+    }
+
+    #[test]
+    fn default() {
+        // Rc is not Sync, so a const rather than a static
+        const _EMPTY: List<i32> = List::new();
+        let list: List<i32> = List::default();
+        assert_eq!(list.iter().next(), None);
+    }
+
+    #[test]
+    fn iter() {
+        let list = List::new().prepend(1).prepend(2).prepend(3);
+
+        let mut iter = list.iter();
+        assert_eq!(iter.next(), Some(&3));
+        let rest = iter.clone();
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(rest.collect::<Vec<_>>(), vec![&2, &1]);
+
+        let mut sum = 0;
+        for elem in &list {
+            sum += elem;
+        }
+        assert_eq!(sum, 6);
+    }
+
+    #[test]
+    fn long_list_drop() {
+        let mut list = List::new();
+        for i in 0..1_000_000 {
+            list = list.prepend(i);
+        }
+        // would overflow the stack with the recursive default drop
+        drop(list);
+    }
+
+    #[test]
+    fn drop_stops_at_shared_nodes() {
+        let shared = List::new().prepend(1).prepend(2);
+        let version = shared.prepend(3).prepend(4);
+        drop(version);
+        assert_eq!(shared.iter().collect::<Vec<_>>(), vec![&2, &1]);
+
+        let version = shared.prepend(3);
+        drop(shared);
+        assert_eq!(version.iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+    }
+
+    #[test]
+    fn arc_list() {
+        let list = ArcList::new().prepend(1).prepend(2).prepend(3);
+        let tail = list.tail();
+
+        // the tail is shared with a thread while this one keeps using the whole list
+        let sum = std::thread::spawn(move || tail.iter().sum::<i32>()).join().unwrap();
+        assert_eq!(sum, 3);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+    }
+
+    #[test]
+    fn arc_lists_dropped_together() {
+        use std::sync::{Arc, Barrier};
+        let mut shared = ArcList::new();
+        for i in 0..200_000 {
+            shared = shared.prepend(i);
+        }
+        // whichever thread lets go of the shared tail last has to free it iteratively, whatever the timing
+        let barrier = Arc::new(Barrier::new(2));
+        let threads: Vec<_> = [shared.prepend(-1), shared.prepend(-2)].into_iter().map(|list| {
+            let barrier = barrier.clone();
+            std::thread::spawn(move || {
+                barrier.wait();
+                drop(list);
+            })
+        }).collect();
+        drop(shared);
+        for thread in threads {
+            thread.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn update() {
+        let v0 = list_from(&[1, 2, 3, 4]);
+        let v1 = v0.update(1, 20);
+        assert_eq!(v1.iter().collect::<Vec<_>>(), vec![&1, &20, &3, &4]);
+        assert_eq!(v0.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4]);
+
+        // the nodes after the edit are the very same ones
+        for (old, new) in v0.iter().zip(v1.iter()).skip(2) {
+            assert!(std::ptr::eq(old, new));
+        }
+        assert!(!std::ptr::eq(v0.head().unwrap(), v1.head().unwrap()));
+
+        let v2 = v1.update(3, 40);
+        assert_eq!(v2.iter().collect::<Vec<_>>(), vec![&1, &20, &3, &40]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn update_out_of_bounds() {
+        list_from(&[1, 2]).update(2, 0);
+    }
+
+    #[test]
+    fn map_filter_fold() {
+        let list = list_from(&[1, 2, 3, 4, 5]);
+
+        let strings = list.map(|elem| elem.to_string());
+        assert_eq!(strings.iter().map(String::as_str).collect::<Vec<_>>(), vec!["1", "2", "3", "4", "5"]);
+        assert_eq!(list.fold(0, |sum, elem| sum + elem), 15);
+
+        // only 2 is removed, so 3 -> 4 -> 5 is reused
+        let filtered = list.filter(|&elem| elem != 2);
+        assert_eq!(filtered.iter().collect::<Vec<_>>(), vec![&1, &3, &4, &5]);
+        assert!(!std::ptr::eq(filtered.head().unwrap(), list.head().unwrap()));
+        for (old, new) in list.iter().skip(2).zip(filtered.iter().skip(1)) {
+            assert!(std::ptr::eq(old, new));
+        }
+
+        // nothing removed: the result is the same list
+        let all = list.filter(|_| true);
+        assert!(std::ptr::eq(all.head().unwrap(), list.head().unwrap()));
+
+        let odd = list.filter(|elem| elem % 2 == 1);
+        assert_eq!(odd.iter().collect::<Vec<_>>(), vec![&1, &3, &5]);
+        assert!(std::ptr::eq(odd.iter().last().unwrap(), list.iter().last().unwrap()));
+        assert_eq!(list.filter(|_| false).head(), None);
+    }
+
+    #[test]
+    fn eq() {
+        let list = list_from(&[1, 2, 3]);
+        assert!(list == list_from(&[1, 2, 3]));
+        assert!(list != list_from(&[1, 2]));
+        assert!(list != list_from(&[1, 2, 4]));
+        assert!(List::<i32>::new() == List::new());
+
+        // == compares every element, shared or not: NaN is never == itself
+        let shared = List::new().prepend(f64::NAN);
+        let a = shared.prepend(1.0);
+        let b = shared.prepend(1.0);
+        assert!(a != b);
+        assert_eq!(a == b, a.iter().eq(b.iter()));
+    }
+
+    #[test]
+    fn fast_eq() {
+        let shared = list_from(&[3, 4, 5]);
+        let a = shared.prepend(2).prepend(1);
+        let b = shared.prepend(2).prepend(1);
+        assert!(a.fast_eq(&b));
+        assert!(a.fast_eq(&a));
+        assert!(a.fast_eq(&list_from(&[1, 2, 3, 4, 5])));
+        assert!(!a.fast_eq(&shared.prepend(0).prepend(1)));
+        assert!(!a.fast_eq(&shared));
+        assert!(List::<i32>::new().fast_eq(&List::new()));
+    }
+
+    #[test]
+    fn rev_and_append() {
+        let list = list_from(&[1, 2, 3]);
+        assert!(list.rev() == list_from(&[3, 2, 1]));
+        assert!(List::<i32>::new().rev() == List::new());
+
+        let other = list_from(&[4, 5]);
+        let both = list.append(&other);
+        assert!(both == list_from(&[1, 2, 3, 4, 5]));
+        // other is reused as it is, self is copied
+        assert!(std::ptr::eq(both.iter().nth(3).unwrap(), other.head().unwrap()));
+        assert!(!std::ptr::eq(both.head().unwrap(), list.head().unwrap()));
+
+        let empty = List::new();
+        assert!(std::ptr::eq(list.append(&empty).head().unwrap(), list.head().unwrap()));
+        assert!(std::ptr::eq(empty.append(&other).head().unwrap(), other.head().unwrap()));
+    }
+
+    #[test]
+    fn len() {
+        let empty = List::<i32>::new();
+        assert_eq!(empty.len(), 0);
+        assert!(empty.is_empty());
+
+        let list = list_from(&[1, 2, 3]);
+        let longer = list.prepend(0);
+        assert_eq!(list.len(), 3);
+        assert_eq!(longer.len(), 4);
+        assert_eq!(longer.tail().tail().len(), 2);
+        assert_eq!(list.update(2, 30).len(), 3);
+        assert_eq!(list.filter(|&elem| elem != 2).len(), 2);
+        assert_eq!(list.append(&longer).len(), 7);
+
+        assert_eq!(longer.get(3), Some(&3));
+        assert_eq!(longer.get(4), None);
+    }
+
+    #[test]
+    fn zipper() {
+        let list = list_from(&[1, 2, 3, 4, 5]);
+        let mut zipper = list.zipper();
+        assert_eq!(zipper.focus(), Some(&1));
+        assert!(!zipper.left());
+
+        assert!(zipper.right());
+        assert!(zipper.right());
+        assert_eq!((zipper.index(), zipper.focus()), (2, Some(&3)));
+        assert_eq!(zipper.set(30), Ok(()));
+        zipper.insert(25);
+        assert_eq!(zipper.focus(), Some(&25));
+        assert!(zipper.left());
+        assert_eq!(zipper.focus(), Some(&2));
+
+        let edited = zipper.rebuild();
+        assert!(edited == list_from(&[1, 2, 25, 30, 4, 5]));
+        assert!(list == list_from(&[1, 2, 3, 4, 5]));
+        // 4 -> 5 was never reached, so it's shared
+        assert!(std::ptr::eq(edited.iter().nth(4).unwrap(), list.iter().nth(3).unwrap()));
+
+        let mut zipper = list.zipper();
+        while zipper.right() {}
+        assert_eq!((zipper.index(), zipper.focus()), (5, None));
+        assert_eq!(zipper.set(0), Err(0));
+        zipper.insert(6);
+        assert!(zipper.rebuild() == list_from(&[1, 2, 3, 4, 5, 6]));
+    }
+
+    #[test]
+    fn copy_on_write() {
+        let mut list = List::new();
+        list.push(3);
+        list.push(2);
+        list.push(1);
+        let snapshot = list.clone();
+        assert!(std::ptr::eq(list.head().unwrap(), snapshot.head().unwrap()));
+
+        // editing position 1 copies the first two nodes, 3 stays shared
+        *list.get_mut(1).unwrap() = 20;
+        assert!(list == list_from(&[1, 20, 3]));
+        assert!(snapshot == list_from(&[1, 2, 3]));
+        assert!(std::ptr::eq(list.get(2).unwrap(), snapshot.get(2).unwrap()));
+
+        // the copies are ours alone now, so editing them again copies nothing
+        let head = list.head().unwrap() as *const i32;
+        *list.peek_mut().unwrap() = 10;
+        assert!(std::ptr::eq(list.head().unwrap(), head));
+        assert_eq!(list.get_mut(3), None);
+
+        assert_eq!(list.pop(), Some(10));
+        assert_eq!(list.pop(), Some(20));
+        // shared with snapshot, so this one is cloned out
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), None);
+        assert!(snapshot == list_from(&[1, 2, 3]));
+        assert_eq!(list.len(), 0);
+    }
+
+    #[test]
+    fn sharing_stats() {
+        let base = list_from(&[3, 4, 5]);
+        let a = base.prepend(2).prepend(1);
+        let b = base.prepend(0);
+        let copy = list_from(&[2, 3, 4, 5]);
+
+        assert_eq!(a.shared_suffix_len(&b), 3);
+        assert_eq!(b.shared_suffix_len(&a), 3);
+        assert_eq!(a.shared_suffix_len(&a), 5);
+        assert_eq!(a.shared_suffix_len(&copy), 0);
+        assert_eq!(a.shared_suffix_len(&List::new()), 0);
+
+        // base itself, plus the nodes 2 and 0 of a and b
+        assert_eq!(base.strong_count_of_head(), 3);
+        assert_eq!(a.strong_count_of_head(), 1);
+        assert_eq!(List::<i32>::new().strong_count_of_head(), 0);
+
+        assert_eq!(List::unique_node_count([&a, &b, &base]), 6);
+        assert_eq!(List::unique_node_count([&a, &copy]), 9);
+        let snapshot = a.clone();
+        assert_eq!(List::unique_node_count([&a, &snapshot]), 5);
+    }
+
+    #[test]
+    fn history() {
+        use super::History;
+        let mut history = History::new(list_from(&[2, 3]));
+        assert!(!history.undo());
+
+        history.current_mut().push(1);
+        history.commit();
+        let before_edit = history.snapshot();
+        *history.current_mut().get_mut(2).unwrap() = 30;
+        history.commit();
+        assert!(*history.current() == list_from(&[1, 2, 30]));
+        // the snapshot taken before the edit didn't change
+        assert!(before_edit == list_from(&[1, 2, 3]));
+
+        assert!(history.undo());
+        assert!(*history.current() == list_from(&[1, 2, 3]));
+        assert!(history.undo());
+        assert!(*history.current() == list_from(&[2, 3]));
+        assert!(!history.undo());
+        assert!(history.redo());
+        assert!(*history.current() == list_from(&[1, 2, 3]));
+
+        // committing after an undo drops the redo branch
+        history.current_mut().pop();
+        history.commit();
+        assert!(!history.redo());
+        assert!(*history.current() == list_from(&[2, 3]));
+
+        // uncommitted edits are dropped by undo
+        history.current_mut().push(0);
+        assert!(history.undo());
+        assert!(*history.current() == list_from(&[1, 2, 3]));
+    }
+}